@@ -0,0 +1,121 @@
+//! Compare the allocations made when parsing a large response into an owned
+//! `Response` and a `borrowed::Response`.
+//!
+//! Run with `cargo bench --bench allocations`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use searchfox_api::{borrowed, Response};
+use serde_json::Value;
+
+/// An allocator that counts the allocations made through it.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// The number of copies of each file in the fixture to put in the large
+/// response.
+const COPIES: usize = 1000;
+
+const ITERATIONS: u32 = 5;
+
+/// Build a large response by repeating each file in the fixture with a unique
+/// path.
+fn large_response() -> String {
+    let mut rsp: Value = serde_json::from_str(include_str!("../tests/BrowserChild.json")).unwrap();
+
+    for section in &["normal", "test", "generated"] {
+        let categories = match rsp[section].as_object_mut() {
+            Some(categories) => categories,
+            None => continue,
+        };
+
+        for raw_matches in categories.values_mut() {
+            let raw_matches = raw_matches.as_array_mut().unwrap();
+            let originals = raw_matches.clone();
+
+            raw_matches.clear();
+            for i in 0..COPIES {
+                for raw_match in &originals {
+                    let mut raw_match = raw_match.clone();
+                    let path = format!("copy{}/{}", i, raw_match["path"].as_str().unwrap());
+                    raw_match["path"] = Value::String(path);
+                    raw_matches.push(raw_match);
+                }
+            }
+        }
+    }
+
+    serde_json::to_string(&rsp).unwrap()
+}
+
+struct Measurement {
+    allocations: usize,
+    bytes: usize,
+    elapsed: Duration,
+}
+
+fn measure<F: Fn()>(f: F) -> Measurement {
+    let mut elapsed = Duration::default();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        f();
+        elapsed += start.elapsed();
+    }
+
+    Measurement {
+        allocations: (ALLOCATIONS.load(Ordering::Relaxed) - allocations) / ITERATIONS as usize,
+        bytes: (ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes) / ITERATIONS as usize,
+        elapsed: elapsed / ITERATIONS,
+    }
+}
+
+fn report(name: &str, m: &Measurement) {
+    println!(
+        "{:<10} {:>12} allocations {:>14} bytes {:>10.2?}",
+        name, m.allocations, m.bytes, m.elapsed
+    );
+}
+
+fn main() {
+    let json = large_response();
+    println!("response size: {} bytes", json.len());
+
+    let owned = measure(|| {
+        let rsp: Response = json.parse().unwrap();
+        drop(rsp);
+    });
+    let borrowed = measure(|| {
+        let rsp = borrowed::Response::from_str(&json).unwrap();
+        drop(rsp);
+    });
+
+    report("owned", &owned);
+    report("borrowed", &borrowed);
+    println!(
+        "borrowed makes {:.1}% of the allocations and {:.1}% of the bytes",
+        100.0 * borrowed.allocations as f64 / owned.allocations as f64,
+        100.0 * borrowed.bytes as f64 / owned.bytes as f64,
+    );
+}
@@ -5,7 +5,43 @@
 // or http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+pub mod borrowed;
+pub mod diff;
+pub mod hit;
+pub mod merge;
+pub mod path;
+pub mod peek;
+pub mod query;
+pub mod repo;
 pub mod response;
+pub mod stats;
+pub mod stream;
+pub mod symbol;
+pub mod tree;
 
+#[cfg(test)]
+mod test_borrowed;
+#[cfg(test)]
+mod test_diff;
+#[cfg(test)]
+mod test_hit;
+#[cfg(test)]
+mod test_merge;
+#[cfg(test)]
+mod test_path;
+#[cfg(test)]
+mod test_peek;
+#[cfg(test)]
+mod test_query;
+#[cfg(test)]
+mod test_repo;
 #[cfg(test)]
 mod test_response;
+#[cfg(test)]
+mod test_stats;
+#[cfg(test)]
+mod test_stream;
+#[cfg(test)]
+mod test_symbol;
+#[cfg(test)]
+mod test_tree;
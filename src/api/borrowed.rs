@@ -0,0 +1,425 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Responses that borrow from the buffer they were parsed from.
+//!
+//! Searchfox responses for common identifiers can be very large. The types in
+//! this module mirror those of the owned [`Response`](../struct.Response.html),
+//! but their strings borrow from the JSON wherever possible instead of being
+//! copied. Strings that contain escape sequences must still be unescaped, so
+//! they are owned.
+//!
+//! The owned types are deserialized through these types, so this module is
+//! the only implementation of the wire format.
+//!
+//! ```
+//! # fn main() -> searchfox_api::Result<()> {
+//! let json = br#"{"*title*": "BrowserChild", "*timedout*": false}"#;
+//! let rsp = searchfox_api::borrowed::Response::from_slice(json)?;
+//! assert_eq!(rsp.title, "BrowserChild");
+//!
+//! let owned: searchfox_api::Response = rsp.into_owned();
+//! assert_eq!(owned.title, "BrowserChild");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+use std::marker::PhantomData;
+
+use indexmap::IndexMap;
+use serde::de::{Deserializer, Error as _, MapAccess, Unexpected, Visitor};
+use serde::Deserialize;
+
+use crate::api::response::{self, category_key, Category};
+use crate::error::{self, Error};
+
+/// A response from Searchfox that borrows from its JSON.
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+pub struct Response<'a> {
+    /// The query that was searched for.
+    #[serde(borrow, rename = "*title*")]
+    pub title: Cow<'a, str>,
+
+    /// Whether or not the request timed out.
+    ///
+    /// If true, the results will be incomplete.
+    #[serde(rename = "*timedout*")]
+    pub timedout: bool,
+
+    /// Matches for the query that occur in code.
+    #[serde(borrow)]
+    pub normal: Option<Matches<'a>>,
+
+    /// Matches for the query that occur in test code.
+    #[serde(borrow)]
+    pub test: Option<Matches<'a>>,
+
+    /// Matches for the query that occur in generated code.
+    #[serde(borrow)]
+    pub generated: Option<Matches<'a>>,
+}
+
+impl<'a> Response<'a> {
+    /// Parse a response from a slice of JSON.
+    pub fn from_slice(v: &'a [u8]) -> error::Result<Self> {
+        serde_json::from_slice(v).map_err(Error::decode)
+    }
+
+    /// Parse a response from a string of JSON.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &'a str) -> error::Result<Self> {
+        serde_json::from_str(s).map_err(Error::decode)
+    }
+
+    /// Reject responses that contain categories of results that are not
+    /// understood.
+    ///
+    /// See [`Response::into_strict`](../struct.Response.html#method.into_strict).
+    pub fn into_strict(self) -> error::Result<Self> {
+        let key = [&self.normal, &self.test, &self.generated]
+            .iter()
+            .filter_map(|matches| matches.as_ref())
+            .filter_map(Matches::unknown_category)
+            .next();
+
+        match key {
+            Some(key) => Err(Error::UnknownCategory { url: None, key }),
+            None => Ok(self),
+        }
+    }
+
+    /// Convert into an owned response.
+    pub fn into_owned(self) -> response::Response {
+        response::Response {
+            title: self.title.into_owned(),
+            timedout: self.timedout,
+            normal: self.normal.map(Matches::into_owned),
+            test: self.test.map(Matches::into_owned),
+            generated: self.generated.map(Matches::into_owned),
+        }
+    }
+}
+
+/// A collection of matches for a query.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Matches<'a> {
+    /// Declarations that match the query.
+    pub declarations: FuzzyMatches<'a>,
+
+    /// Definitions that match the query.
+    pub definitions: FuzzyMatches<'a>,
+
+    /// File names that match the query.
+    pub files: Vec<Cow<'a, str>>,
+
+    /// Plain text that matches the query.
+    pub text_matches: FileMatches<'a>,
+
+    /// Code use that match the query.
+    pub uses: FuzzyMatches<'a>,
+
+    /// Matches in categories that are not otherwise understood.
+    ///
+    /// See [`Matches::other`](../struct.Matches.html#structfield.other).
+    pub other: Vec<(Cow<'a, str>, Cow<'a, str>, FileMatches<'a>)>,
+
+    /// The keys of the categories of results in the order that Searchfox
+    /// returned them.
+    ///
+    /// See [`Matches::category_order`](../struct.Matches.html#structfield.category_order).
+    pub category_order: Vec<Cow<'a, str>>,
+}
+
+impl<'a> Matches<'a> {
+    /// Add the matches for a category of results.
+    ///
+    /// Parts of the category's key are converted with `cow`, so that they can
+    /// borrow from the key if it was borrowed.
+    fn insert<'k, F>(&mut self, category: Category<'k>, value: Vec<RawMatch<'a>>, cow: F)
+    where
+        F: Fn(&'k str) -> Cow<'a, str>,
+    {
+        match category {
+            Category::Files => self.files = RawMatch::into_paths(value),
+            Category::Text => self.text_matches = RawMatch::into_file_matches(value),
+            Category::Definitions(name) => {
+                self.definitions
+                    .insert(cow(name), RawMatch::into_file_matches(value));
+            }
+            Category::Declarations(name) => {
+                self.declarations
+                    .insert(cow(name), RawMatch::into_file_matches(value));
+            }
+            Category::Uses(name) => {
+                self.uses
+                    .insert(cow(name), RawMatch::into_file_matches(value));
+            }
+            Category::Other(kind, symbol) => {
+                self.other
+                    .push((cow(kind), cow(symbol), RawMatch::into_file_matches(value)));
+            }
+        }
+    }
+
+    /// The key of the first category of results that is not understood.
+    fn unknown_category(&self) -> Option<String> {
+        self.other
+            .first()
+            .map(|(kind, symbol, _)| category_key(kind, symbol))
+    }
+
+    /// Convert into owned matches.
+    pub fn into_owned(self) -> response::Matches {
+        response::Matches {
+            declarations: fuzzy_into_owned(self.declarations),
+            definitions: fuzzy_into_owned(self.definitions),
+            files: self.files.into_iter().map(Cow::into_owned).collect(),
+            text_matches: file_into_owned(self.text_matches),
+            uses: fuzzy_into_owned(self.uses),
+            other: self
+                .other
+                .into_iter()
+                .map(|(kind, symbol, matches)| {
+                    (
+                        kind.into_owned(),
+                        symbol.into_owned(),
+                        file_into_owned(matches),
+                    )
+                })
+                .collect(),
+            category_order: self
+                .category_order
+                .into_iter()
+                .map(Cow::into_owned)
+                .collect(),
+        }
+    }
+}
+
+/// Fuzzily found matches.
+pub type FuzzyMatches<'a> = IndexMap<Cow<'a, str>, FileMatches<'a>>;
+
+/// A mapping of file names to query matches found within those files.
+pub type FileMatches<'a> = IndexMap<Cow<'a, str>, Vec<LineMatch<'a>>>;
+
+fn fuzzy_into_owned(matches: FuzzyMatches) -> response::FuzzyMatches {
+    matches
+        .into_iter()
+        .map(|(symbol, matches)| (symbol.into_owned(), file_into_owned(matches)))
+        .collect()
+}
+
+fn file_into_owned(matches: FileMatches) -> response::FileMatches {
+    matches
+        .into_iter()
+        .map(|(path, lines)| {
+            (
+                path.into_owned(),
+                lines.into_iter().map(LineMatch::into_owned).collect(),
+            )
+        })
+        .collect()
+}
+
+/// Context about a match.
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+pub struct MatchContext<'a> {
+    /// The context where the match was found.
+    #[serde(borrow)]
+    pub context: Cow<'a, str>,
+
+    /// The symbol that Searchfox has generated for this context.
+    #[serde(borrow, rename = "contextsym")]
+    pub symbol: Cow<'a, str>,
+}
+
+impl MatchContext<'_> {
+    /// Convert into an owned context.
+    pub fn into_owned(self) -> response::MatchContext {
+        response::MatchContext {
+            context: self.context.into_owned(),
+            symbol: self.symbol.into_owned(),
+        }
+    }
+}
+
+/// A match for the query.
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+pub struct LineMatch<'a> {
+    /// The contents of the line.
+    #[serde(borrow)]
+    pub line: Cow<'a, str>,
+
+    /// The line number in the file.
+    #[serde(rename = "lno")]
+    pub number: usize,
+
+    /// The position in the line where the symbol was found.
+    pub bounds: (usize, usize),
+
+    /// Lines before and/or after the symbol that may provide some context.
+    #[serde(
+        borrow,
+        default,
+        rename = "peekLines",
+        deserialize_with = "deserialize_optional_str"
+    )]
+    pub peek_lines: Option<Cow<'a, str>>,
+
+    /// Context for where the match was found.
+    #[serde(
+        borrow,
+        flatten,
+        default,
+        deserialize_with = "deserialize_optional_context"
+    )]
+    pub context: Option<MatchContext<'a>>,
+}
+
+impl LineMatch<'_> {
+    /// Convert into an owned match.
+    pub fn into_owned(self) -> response::LineMatch {
+        response::LineMatch {
+            line: self.line.into_owned(),
+            number: self.number,
+            bounds: self.bounds,
+            peek_lines: self.peek_lines.map(Cow::into_owned),
+            context: self.context.map(MatchContext::into_owned),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawMatch<'a> {
+    #[serde(borrow)]
+    lines: Vec<LineMatch<'a>>,
+
+    #[serde(borrow)]
+    path: Cow<'a, str>,
+}
+
+impl<'a> RawMatch<'a> {
+    fn into_file_matches(matches: Vec<RawMatch<'a>>) -> FileMatches<'a> {
+        matches.into_iter().map(|m| (m.path, m.lines)).collect()
+    }
+
+    fn into_paths(matches: Vec<RawMatch<'a>>) -> Vec<Cow<'a, str>> {
+        matches.into_iter().map(|m| m.path).collect()
+    }
+}
+
+/// A string that is borrowed from the input when possible.
+struct CowStr<'a>(Cow<'a, str>);
+
+impl<'de> Deserialize<'de> for CowStr<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(CowStrVisitor)
+    }
+}
+
+struct CowStrVisitor;
+impl<'de> Visitor<'de> for CowStrVisitor {
+    type Value = CowStr<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(CowStr(Cow::Borrowed(v)))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(CowStr(Cow::Owned(v.into())))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(CowStr(Cow::Owned(v)))
+    }
+}
+
+struct MatchesVisitor<'a>(PhantomData<Matches<'a>>);
+impl<'de: 'a, 'a> Visitor<'de> for MatchesVisitor<'a> {
+    type Value = Matches<'a>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of matches")
+    }
+
+    fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut matches = Matches::default();
+
+        while let Some((CowStr(key), value)) =
+            access.next_entry::<CowStr<'de>, Vec<RawMatch<'a>>>()?
+        {
+            match key {
+                Cow::Borrowed(key) => {
+                    matches.insert(Category::of(key), value, Cow::Borrowed);
+                    matches.category_order.push(Cow::Borrowed(key));
+                }
+                Cow::Owned(key) => {
+                    matches.insert(Category::of(&key), value, |s| Cow::Owned(s.into()));
+                    matches.category_order.push(Cow::Owned(key));
+                }
+            }
+        }
+
+        Ok(matches)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for Matches<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(MatchesVisitor(PhantomData))
+    }
+}
+
+/// Deserialize an `Option<Cow<str>>` where the empty string is `None`.
+fn deserialize_optional_str<'de, D>(deserializer: D) -> Result<Option<Cow<'de, str>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match <Option<CowStr> as Deserialize>::deserialize(deserializer)? {
+        Some(CowStr(ref s)) if s.is_empty() => Ok(None),
+        Some(CowStr(s)) => Ok(Some(s)),
+        None => Ok(None),
+    }
+}
+
+fn deserialize_optional_context<'de, D>(
+    deserializer: D,
+) -> Result<Option<MatchContext<'de>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match <Option<MatchContext> as Deserialize>::deserialize(deserializer)? {
+        Some(ctx) => match (ctx.context.len(), ctx.symbol.len()) {
+            (0, 0) => Ok(None),
+            (0, _) => Err(D::Error::invalid_value(
+                Unexpected::Str(&ctx.symbol),
+                &"an empty symbol",
+            )),
+            (_, 0) => Err(D::Error::invalid_value(
+                Unexpected::Str(&ctx.symbol),
+                &"a non-empty symbol",
+            )),
+            (_, _) => Ok(Some(ctx)),
+        },
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,390 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+
+use indexmap::{IndexMap, IndexSet};
+use serde::Serialize;
+
+use crate::api::hit::Section;
+use crate::api::response::{LineMatch, Response};
+
+/// The differences between two responses.
+///
+/// Responses are compared category by category, e.g., all of the `Uses` in
+/// the `normal` section. Line matches are matched up by their contents, so
+/// that a match whose line number changed (e.g., because lines were added
+/// above it) is reported as moved rather than as removed and added.
+///
+/// The diff can be rendered for humans with its `Display` implementation or
+/// serialized (e.g., to JSON).
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct ResponseDiff {
+    /// The categories that differ, in the order they appear in the new
+    /// response followed by those only in the old response.
+    pub categories: Vec<CategoryDiff>,
+}
+
+/// The differences in a single category of results.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct CategoryDiff {
+    /// The section of the response.
+    pub section: Section,
+
+    /// The kind of category, e.g., `"Uses"` or `"Files"`.
+    pub kind: String,
+
+    /// Symbols that only have matches in the new response.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added_symbols: Vec<String>,
+
+    /// Symbols that only have matches in the old response.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed_symbols: Vec<String>,
+
+    /// Files that only have matches in the new response.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added_files: Vec<String>,
+
+    /// Files that only have matches in the old response.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed_files: Vec<String>,
+
+    /// Line matches only in the new response.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added_hits: Vec<HitChange>,
+
+    /// Line matches only in the old response.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed_hits: Vec<HitChange>,
+
+    /// Line matches in both responses whose line numbers changed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub moved_hits: Vec<MovedHit>,
+}
+
+impl CategoryDiff {
+    fn new(section: Section, kind: &str) -> Self {
+        CategoryDiff {
+            section,
+            kind: kind.into(),
+            added_symbols: vec![],
+            removed_symbols: vec![],
+            added_files: vec![],
+            removed_files: vec![],
+            added_hits: vec![],
+            removed_hits: vec![],
+            moved_hits: vec![],
+        }
+    }
+
+    /// Whether or not there are no differences.
+    pub fn is_empty(&self) -> bool {
+        self.added_symbols.is_empty()
+            && self.removed_symbols.is_empty()
+            && self.added_files.is_empty()
+            && self.removed_files.is_empty()
+            && self.added_hits.is_empty()
+            && self.removed_hits.is_empty()
+            && self.moved_hits.is_empty()
+    }
+}
+
+/// A line match that was added or removed.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct HitChange {
+    /// The symbol that was matched, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+
+    /// The path of the file.
+    pub path: String,
+
+    /// The line number in the file.
+    pub number: usize,
+
+    /// The contents of the line.
+    pub line: String,
+}
+
+/// A line match whose line number changed.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct MovedHit {
+    /// The symbol that was matched, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+
+    /// The path of the file.
+    pub path: String,
+
+    /// The line number in the old response.
+    pub old_number: usize,
+
+    /// The line number in the new response.
+    pub new_number: usize,
+
+    /// The contents of the line.
+    pub line: String,
+}
+
+/// The line matches in a response, grouped by section and category kind.
+type Grouped<'a> = IndexMap<(Section, &'a str), Symbols<'a>>;
+
+/// The line matches in a category, grouped by symbol and then by file.
+type Symbols<'a> = IndexMap<Option<&'a str>, IndexMap<&'a str, Vec<&'a LineMatch>>>;
+
+fn group(rsp: &Response) -> Grouped<'_> {
+    let mut grouped = Grouped::new();
+
+    for hit in rsp.hits() {
+        let lines = grouped
+            .entry((hit.section, hit.kind.category_kind()))
+            .or_default()
+            .entry(hit.symbol)
+            .or_default()
+            .entry(hit.path)
+            .or_default();
+
+        if let Some(line) = hit.line {
+            lines.push(line);
+        }
+    }
+
+    grouped
+}
+
+/// Iterate over the keys of `new` followed by the keys only in `old`.
+fn union<'a, K, V>(
+    old: &'a IndexMap<K, V>,
+    new: &'a IndexMap<K, V>,
+) -> impl Iterator<Item = &'a K> + 'a
+where
+    K: Eq + Hash,
+{
+    new.keys()
+        .chain(old.keys().filter(move |key| !new.contains_key(*key)))
+}
+
+impl ResponseDiff {
+    /// Compare an old response with a new one.
+    pub fn new(old: &Response, new: &Response) -> Self {
+        let old = group(old);
+        let new = group(new);
+        let empty = IndexMap::new();
+
+        let categories = union(&old, &new)
+            .map(|&(section, kind)| {
+                let old_symbols = old.get(&(section, kind)).unwrap_or(&empty);
+                let new_symbols = new.get(&(section, kind)).unwrap_or(&empty);
+                let mut diff = CategoryDiff::new(section, kind);
+
+                diff_symbols(&mut diff, old_symbols, new_symbols);
+                diff
+            })
+            .filter(|diff| !diff.is_empty())
+            .collect();
+
+        ResponseDiff { categories }
+    }
+
+    /// Whether or not the responses have the same matches.
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+    }
+}
+
+fn diff_symbols(diff: &mut CategoryDiff, old: &Symbols, new: &Symbols) {
+    let empty = IndexMap::new();
+
+    for &symbol in union(old, new) {
+        let old_files = old.get(&symbol);
+        let new_files = new.get(&symbol);
+
+        if let Some(name) = symbol {
+            match (old_files, new_files) {
+                (None, Some(_)) => diff.added_symbols.push(name.into()),
+                (Some(_), None) => diff.removed_symbols.push(name.into()),
+                _ => {}
+            }
+        }
+
+        let old_files = old_files.unwrap_or(&empty);
+        let new_files = new_files.unwrap_or(&empty);
+
+        for &path in union(old_files, new_files) {
+            diff_lines(
+                diff,
+                symbol,
+                path,
+                old_files.get(path).map_or(&[], Vec::as_slice),
+                new_files.get(path).map_or(&[], Vec::as_slice),
+            );
+        }
+    }
+
+    // A file is added or removed if it has no matches in the category (for
+    // any symbol) in the other response.
+    let old_files = files(old);
+    let new_files = files(new);
+
+    diff.added_files = new_files
+        .iter()
+        .filter(|path| !old_files.contains(*path))
+        .map(|&path| path.into())
+        .collect();
+    diff.removed_files = old_files
+        .iter()
+        .filter(|path| !new_files.contains(*path))
+        .map(|&path| path.into())
+        .collect();
+}
+
+/// The files with matches in a category, in order.
+fn files<'a>(symbols: &Symbols<'a>) -> IndexSet<&'a str> {
+    symbols.values().flat_map(IndexMap::keys).cloned().collect()
+}
+
+/// Compare the line matches in a single file.
+///
+/// Lines are paired up by their contents and bounds, in order.
+fn diff_lines(
+    diff: &mut CategoryDiff,
+    symbol: Option<&str>,
+    path: &str,
+    old: &[&LineMatch],
+    new: &[&LineMatch],
+) {
+    // The indices of the old lines, by contents and bounds, in order.
+    let mut unmatched: HashMap<(&str, (usize, usize)), VecDeque<usize>> = HashMap::new();
+    for (idx, line) in old.iter().enumerate() {
+        unmatched
+            .entry((line.line.as_str(), line.bounds))
+            .or_default()
+            .push_back(idx);
+    }
+
+    let mut matched = vec![false; old.len()];
+
+    for line in new {
+        let idx = unmatched
+            .get_mut(&(line.line.as_str(), line.bounds))
+            .and_then(VecDeque::pop_front);
+
+        match idx {
+            Some(idx) => {
+                matched[idx] = true;
+
+                let old = old[idx];
+                if old.number != line.number {
+                    diff.moved_hits.push(MovedHit {
+                        symbol: symbol.map(Into::into),
+                        path: path.into(),
+                        old_number: old.number,
+                        new_number: line.number,
+                        line: line.line.clone(),
+                    });
+                }
+            }
+            None => diff.added_hits.push(HitChange::new(symbol, path, line)),
+        }
+    }
+
+    diff.removed_hits.extend(
+        old.iter()
+            .zip(matched)
+            .filter(|&(_, matched)| !matched)
+            .map(|(line, _)| HitChange::new(symbol, path, line)),
+    );
+}
+
+impl HitChange {
+    fn new(symbol: Option<&str>, path: &str, line: &LineMatch) -> Self {
+        HitChange {
+            symbol: symbol.map(Into::into),
+            path: path.into(),
+            number: line.number,
+            line: line.line.clone(),
+        }
+    }
+}
+
+impl fmt::Display for ResponseDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, category) in self.categories.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{}", category)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for CategoryDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}: {}", self.section, self.kind)?;
+
+        for symbol in &self.added_symbols {
+            writeln!(f, "+ symbol {}", symbol)?;
+        }
+        for symbol in &self.removed_symbols {
+            writeln!(f, "- symbol {}", symbol)?;
+        }
+        for path in &self.added_files {
+            writeln!(f, "+ file {}", path)?;
+        }
+        for path in &self.removed_files {
+            writeln!(f, "- file {}", path)?;
+        }
+        for hit in &self.added_hits {
+            writeln!(f, "+ {}", hit)?;
+        }
+        for hit in &self.removed_hits {
+            writeln!(f, "- {}", hit)?;
+        }
+        for hit in &self.moved_hits {
+            writeln!(f, "~ {}", hit)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for HitChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.path, self.number, self.line.trim())?;
+
+        if let Some(ref symbol) = self.symbol {
+            write!(f, " [{}]", symbol)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for MovedHit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}:{} -> {}: {}",
+            self.path,
+            self.old_number,
+            self.new_number,
+            self.line.trim()
+        )?;
+
+        if let Some(ref symbol) = self.symbol {
+            write!(f, " [{}]", symbol)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,267 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::api::response::{category_key, FileMatches, FuzzyMatches, LineMatch, Matches, Response};
+
+/// A section of a response.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Section {
+    /// Matches that occur in code.
+    Normal,
+
+    /// Matches that occur in test code.
+    Test,
+
+    /// Matches that occur in generated code.
+    Generated,
+}
+
+impl Section {
+    /// All sections, in the order they appear in a response.
+    pub fn all() -> &'static [Section] {
+        &[Section::Normal, Section::Test, Section::Generated]
+    }
+
+    /// The name of the section in a response.
+    pub fn name(self) -> &'static str {
+        match self {
+            Section::Normal => "normal",
+            Section::Test => "test",
+            Section::Generated => "generated",
+        }
+    }
+}
+
+impl fmt::Display for Section {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The kind of a hit.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum HitKind<'a> {
+    /// A file name that matched the query.
+    File,
+
+    /// Plain text that matched the query.
+    Text,
+
+    /// A definition that matched the query.
+    Definition,
+
+    /// A declaration that matched the query.
+    Declaration,
+
+    /// A use that matched the query.
+    Use,
+
+    /// A match in a category that is not otherwise understood.
+    Other(&'a str),
+}
+
+impl<'a> HitKind<'a> {
+    /// A human-readable name for the kind of hit.
+    pub fn name(&self) -> &str {
+        match self {
+            HitKind::File => "File",
+            HitKind::Text => "Text",
+            HitKind::Definition => "Definition",
+            HitKind::Declaration => "Declaration",
+            HitKind::Use => "Use",
+            HitKind::Other(kind) => kind,
+        }
+    }
+
+    /// The kind of the category of results in a response, e.g., `"Uses"`.
+    pub(crate) fn category_kind(self) -> &'a str {
+        match self {
+            HitKind::File => "Files",
+            HitKind::Text => "Textual Occurrences",
+            HitKind::Definition => "Definitions",
+            HitKind::Declaration => "Declarations",
+            HitKind::Use => "Uses",
+            HitKind::Other(kind) => kind,
+        }
+    }
+}
+
+impl fmt::Display for HitKind<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A single hit in a response.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Hit<'a> {
+    /// The section of the response that the hit was found in.
+    pub section: Section,
+
+    /// The kind of hit.
+    pub kind: HitKind<'a>,
+
+    /// The symbol that was matched.
+    ///
+    /// This is `None` for file and text hits.
+    pub symbol: Option<&'a str>,
+
+    /// The path of the file that the hit was found in.
+    pub path: &'a str,
+
+    /// The matching line.
+    ///
+    /// This is `None` for file hits.
+    pub line: Option<&'a LineMatch>,
+}
+
+impl Hit<'_> {
+    /// The key of the category of results that the hit was found in, as it
+    /// appears in a response, e.g., `"Uses (BrowserChild)"`.
+    pub fn category(&self) -> String {
+        category_key(self.kind.category_kind(), self.symbol.unwrap_or(""))
+    }
+}
+
+/// An iterator over the hits in a response.
+///
+/// This is created by [`Response::hits`](struct.Response.html#method.hits).
+pub struct Hits<'a> {
+    inner: Box<dyn Iterator<Item = Hit<'a>> + 'a>,
+}
+
+impl<'a> Hits<'a> {
+    fn new<I>(inner: I) -> Self
+    where
+        I: Iterator<Item = Hit<'a>> + 'a,
+    {
+        Hits {
+            inner: Box::new(inner),
+        }
+    }
+
+    /// Only yield hits in the given section.
+    pub fn section(self, section: Section) -> Self {
+        Hits::new(self.inner.filter(move |hit| hit.section == section))
+    }
+
+    /// Only yield hits of the given kind.
+    pub fn kind(self, kind: HitKind<'a>) -> Self {
+        Hits::new(self.inner.filter(move |hit| hit.kind == kind))
+    }
+
+    /// Only yield hits in files whose paths begin with the given prefix.
+    pub fn path_prefix(self, prefix: &'a str) -> Self {
+        Hits::new(self.inner.filter(move |hit| hit.path.starts_with(prefix)))
+    }
+}
+
+impl<'a> Iterator for Hits<'a> {
+    type Item = Hit<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl fmt::Debug for Hits<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Hits").finish()
+    }
+}
+
+impl Response {
+    /// The matches in the given section, if any.
+    pub fn section(&self, section: Section) -> Option<&Matches> {
+        match section {
+            Section::Normal => self.normal.as_ref(),
+            Section::Test => self.test.as_ref(),
+            Section::Generated => self.generated.as_ref(),
+        }
+    }
+
+    /// The sections in the response that have matches.
+    pub fn sections(&self) -> impl Iterator<Item = (Section, &Matches)> {
+        Section::all()
+            .iter()
+            .filter_map(move |&section| self.section(section).map(|m| (section, m)))
+    }
+
+    /// Iterate over every hit in the response.
+    ///
+    /// Hits are yielded by section, then by kind, in the order that Searchfox
+    /// returned them.
+    pub fn hits(&self) -> Hits<'_> {
+        Hits::new(
+            self.sections()
+                .flat_map(|(section, matches)| matches.hits(section)),
+        )
+    }
+}
+
+impl Matches {
+    /// Iterate over every hit, as if they were in the given section.
+    pub fn hits(&self, section: Section) -> Hits<'_> {
+        let files = self.files.iter().map(move |path| Hit {
+            section,
+            kind: HitKind::File,
+            symbol: None,
+            path,
+            line: None,
+        });
+
+        let other = self.other.iter().flat_map(move |(kind, symbol, matches)| {
+            let symbol = Some(symbol.as_str()).filter(|s| !s.is_empty());
+            file_hits(section, HitKind::Other(kind), symbol, matches)
+        });
+
+        Hits::new(
+            files
+                .chain(file_hits(section, HitKind::Text, None, &self.text_matches))
+                .chain(fuzzy_hits(section, HitKind::Definition, &self.definitions))
+                .chain(fuzzy_hits(
+                    section,
+                    HitKind::Declaration,
+                    &self.declarations,
+                ))
+                .chain(fuzzy_hits(section, HitKind::Use, &self.uses))
+                .chain(other),
+        )
+    }
+}
+
+fn fuzzy_hits<'a>(
+    section: Section,
+    kind: HitKind<'a>,
+    matches: &'a FuzzyMatches,
+) -> impl Iterator<Item = Hit<'a>> + 'a {
+    matches
+        .iter()
+        .flat_map(move |(symbol, matches)| file_hits(section, kind, Some(symbol), matches))
+}
+
+fn file_hits<'a>(
+    section: Section,
+    kind: HitKind<'a>,
+    symbol: Option<&'a str>,
+    matches: &'a FileMatches,
+) -> impl Iterator<Item = Hit<'a>> + 'a {
+    matches.iter().flat_map(move |(path, lines)| {
+        lines.iter().map(move |line| Hit {
+            section,
+            kind,
+            symbol,
+            path,
+            line: Some(line),
+        })
+    })
+}
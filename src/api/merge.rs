@@ -0,0 +1,218 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::collections::{HashMap, HashSet};
+use std::iter::FromIterator;
+
+use crate::api::hit::{Hit, Section};
+use crate::api::response::{FileMatches, FuzzyMatches, Matches, Response};
+
+impl Response {
+    /// Merge another response into this one.
+    ///
+    /// The matches in each section are merged with
+    /// [`Matches::merge`](struct.Matches.html#method.merge). The merged
+    /// response has timed out if either response timed out. The title of this
+    /// response is kept.
+    pub fn merge(&mut self, other: Response) {
+        self.timedout |= other.timedout;
+
+        merge_section(&mut self.normal, other.normal);
+        merge_section(&mut self.test, other.test);
+        merge_section(&mut self.generated, other.generated);
+    }
+}
+
+fn merge_section(matches: &mut Option<Matches>, other: Option<Matches>) {
+    match (matches.as_mut(), other) {
+        (Some(matches), Some(other)) => matches.merge(other),
+        (None, Some(other)) => *matches = Some(other),
+        (_, None) => {}
+    }
+}
+
+impl Matches {
+    /// Merge other matches into these.
+    ///
+    /// Files and symbols that only occur in `other` are added after those
+    /// already present. Within a category, line matches in the same file with
+    /// the same line number and bounds are duplicates and only the first is
+    /// kept, whether they come from these matches or from `other`.
+    ///
+    /// Categories that only occur in `other` are ordered after those already
+    /// present.
+    pub fn merge(&mut self, other: Matches) {
+        let mut files: HashSet<String> = self.files.iter().cloned().collect();
+        for path in other.files {
+            if !files.contains(&path) {
+                files.insert(path.clone());
+                self.files.push(path);
+            }
+        }
+
+        merge_file_matches(&mut self.text_matches, other.text_matches);
+        merge_fuzzy_matches(&mut self.definitions, other.definitions);
+        merge_fuzzy_matches(&mut self.declarations, other.declarations);
+        merge_fuzzy_matches(&mut self.uses, other.uses);
+
+        for (kind, symbol, file_matches) in other.other {
+            let existing = self
+                .other
+                .iter_mut()
+                .find(|(k, s, _)| *k == kind && *s == symbol);
+
+            match existing {
+                Some((_, _, existing)) => merge_file_matches(existing, file_matches),
+                None => self.other.push((kind, symbol, file_matches)),
+            }
+        }
+
+        let mut keys: HashSet<String> = self.category_order.iter().cloned().collect();
+        for key in other.category_order {
+            if !keys.contains(&key) {
+                keys.insert(key.clone());
+                self.category_order.push(key);
+            }
+        }
+    }
+}
+
+fn merge_fuzzy_matches(matches: &mut FuzzyMatches, other: FuzzyMatches) {
+    for (symbol, file_matches) in other {
+        merge_file_matches(matches.entry(symbol).or_default(), file_matches);
+    }
+}
+
+fn merge_file_matches(matches: &mut FileMatches, other: FileMatches) {
+    for (path, lines) in other {
+        let existing = matches.entry(path).or_default();
+        let mut seen = HashSet::with_capacity(existing.len() + lines.len());
+
+        existing.retain(|line| seen.insert((line.number, line.bounds)));
+        existing.extend(
+            lines
+                .into_iter()
+                .filter(|line| seen.insert((line.number, line.bounds))),
+        );
+    }
+}
+
+/// Identifies a hit independently of the response it came from.
+#[derive(Debug, Eq, Hash, PartialEq)]
+struct HitKey {
+    section: Section,
+    category: String,
+    path: String,
+    line: Option<(usize, (usize, usize))>,
+}
+
+impl HitKey {
+    fn new(hit: &Hit) -> Self {
+        HitKey {
+            section: hit.section,
+            category: hit.category(),
+            path: hit.path.into(),
+            line: hit.line.map(|line| (line.number, line.bounds)),
+        }
+    }
+}
+
+/// The result of merging several responses.
+///
+/// This records which of the queries each hit in the merged response came
+/// from. Queries are identified by a label given when each response is
+/// added, since Searchfox gives the same title to queries that differ only in
+/// their options (e.g., a plain query and its regular expression variant).
+#[derive(Debug, Default)]
+pub struct MergedResponse {
+    response: Option<Response>,
+    queries: Vec<String>,
+    sources: HashMap<HitKey, Vec<usize>>,
+}
+
+impl MergedResponse {
+    /// Create an empty merged response.
+    pub fn new() -> Self {
+        MergedResponse::default()
+    }
+
+    /// Merge the response to a query.
+    ///
+    /// The query is identified by `label`, e.g., the URL of a
+    /// [`SearchQuery`](struct.SearchQuery.html). Responses added with the same
+    /// label are treated as coming from the same query.
+    pub fn add<S: Into<String>>(&mut self, label: S, response: Response) {
+        let label = label.into();
+        let query = match self.queries.iter().position(|q| *q == label) {
+            Some(idx) => idx,
+            None => {
+                self.queries.push(label);
+                self.queries.len() - 1
+            }
+        };
+
+        for hit in response.hits() {
+            let sources = self.sources.entry(HitKey::new(&hit)).or_default();
+
+            if !sources.contains(&query) {
+                sources.push(query);
+            }
+        }
+
+        match self.response {
+            Some(ref mut merged) => merged.merge(response),
+            None => self.response = Some(response),
+        }
+    }
+
+    /// The merged response, if any responses have been merged.
+    pub fn response(&self) -> Option<&Response> {
+        self.response.as_ref()
+    }
+
+    /// Consume the merged response, returning the underlying response.
+    pub fn into_response(self) -> Option<Response> {
+        self.response
+    }
+
+    /// The labels of the queries that have been merged, in the order they
+    /// were added.
+    pub fn queries(&self) -> &[String] {
+        &self.queries
+    }
+
+    /// The labels of the queries that the given hit came from.
+    ///
+    /// This is empty if the hit is not in the merged response.
+    pub fn sources(&self, hit: &Hit) -> Vec<&str> {
+        self.sources
+            .get(&HitKey::new(hit))
+            .map(|sources| {
+                sources
+                    .iter()
+                    .map(|&idx| self.queries[idx].as_str())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, Response)> for MergedResponse {
+    /// Merge responses, each labelled with its query.
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (S, Response)>,
+    {
+        let mut merged = MergedResponse::new();
+
+        for (label, response) in iter {
+            merged.add(label, response);
+        }
+
+        merged
+    }
+}
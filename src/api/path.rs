@@ -0,0 +1,165 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::fmt;
+
+use crate::api::hit::{Hit, Section};
+
+/// The prefix of the paths of generated files.
+pub static GENERATED_PREFIX: &str = "__GENERATED__/";
+
+/// Directory names that Searchfox considers to contain tests.
+///
+/// These are compared case-insensitively against whole components of a path.
+static TEST_DIRECTORIES: &[&str] = &[
+    "test",
+    "tests",
+    "mochitest",
+    "unit",
+    "gtest",
+    "testing",
+    "jit-test",
+];
+
+/// Directories in mozilla-central that contain third-party code.
+///
+/// These are compared case-insensitively against the start of a path.
+static THIRD_PARTY_PREFIXES: &[&str] = &[
+    "gfx/angle/",
+    "gfx/cairo/",
+    "gfx/graphite2/",
+    "gfx/harfbuzz/",
+    "gfx/ots/",
+    "gfx/skia/",
+    "intl/icu/",
+    "media/ffvpx/",
+    "media/libaom/",
+    "media/libjpeg/",
+    "media/libpng/",
+    "media/libvpx/",
+    "media/libwebp/",
+    "modules/brotli/",
+    "modules/freetype2/",
+    "modules/woff2/",
+    "modules/zlib/",
+    "nsprpub/",
+    "other-licenses/",
+    "security/nss/",
+    "third_party/",
+];
+
+/// The interface definition language of a file.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum IdlKind {
+    /// XPIDL (`.idl`).
+    Xpidl,
+
+    /// WebIDL (`.webidl`).
+    WebIdl,
+
+    /// IPDL (`.ipdl` and `.ipdlh`).
+    Ipdl,
+}
+
+impl IdlKind {
+    /// The name of the language.
+    pub fn name(self) -> &'static str {
+        match self {
+            IdlKind::Xpidl => "XPIDL",
+            IdlKind::WebIdl => "WebIDL",
+            IdlKind::Ipdl => "IPDL",
+        }
+    }
+}
+
+impl fmt::Display for IdlKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The classification of a path in a repository.
+///
+/// Classifications are not exclusive: e.g., a test may also be third-party
+/// code.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PathKind {
+    generated: bool,
+    test: bool,
+    third_party: bool,
+    idl: Option<IdlKind>,
+}
+
+impl PathKind {
+    /// Classify a path.
+    pub fn classify(path: &str) -> Self {
+        let lower = path.to_ascii_lowercase();
+
+        let mut directories = lower.split('/');
+        directories.next_back();
+
+        let idl = match lower.rsplit('.').next() {
+            Some("idl") => Some(IdlKind::Xpidl),
+            Some("webidl") => Some(IdlKind::WebIdl),
+            Some("ipdl") | Some("ipdlh") => Some(IdlKind::Ipdl),
+            _ => None,
+        };
+
+        PathKind {
+            generated: path.starts_with(GENERATED_PREFIX),
+            test: directories.any(|directory| TEST_DIRECTORIES.contains(&directory)),
+            third_party: THIRD_PARTY_PREFIXES
+                .iter()
+                .any(|prefix| lower.starts_with(prefix))
+                || lower.split('/').any(|component| component == "third_party"),
+            idl,
+        }
+    }
+
+    /// Whether or not the file is generated during the build.
+    ///
+    /// Generated files are under `__GENERATED__/`.
+    pub fn is_generated(&self) -> bool {
+        self.generated
+    }
+
+    /// Whether or not the file is a test, according to the same heuristics
+    /// that Searchfox uses.
+    pub fn is_test(&self) -> bool {
+        self.test
+    }
+
+    /// Whether or not the file is third-party code.
+    pub fn is_third_party(&self) -> bool {
+        self.third_party
+    }
+
+    /// The interface definition language of the file, if any.
+    pub fn idl(&self) -> Option<IdlKind> {
+        self.idl
+    }
+
+    /// The section of a response that Searchfox puts matches in the file in.
+    ///
+    /// Generated files take precedence over tests.
+    pub fn section(&self) -> Section {
+        if self.generated {
+            Section::Generated
+        } else if self.test {
+            Section::Test
+        } else {
+            Section::Normal
+        }
+    }
+}
+
+impl Hit<'_> {
+    /// Classify the path of the hit.
+    pub fn path_kind(&self) -> PathKind {
+        PathKind::classify(self.path)
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::api::response::LineMatch;
+
+/// A single line of [`PeekLines`](struct.PeekLines.html).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PeekLine<'a> {
+    /// The line number in the file.
+    pub number: usize,
+
+    /// The contents of the line.
+    pub text: &'a str,
+}
+
+/// The parsed `peekLines` of a match.
+///
+/// Searchfox includes the leading doc comment and the full declaration (which
+/// may span several lines) for some matches.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeekLines<'a> {
+    lines: Vec<PeekLine<'a>>,
+    doc_len: usize,
+    match_index: usize,
+}
+
+impl<'a> PeekLines<'a> {
+    /// Parse the `peekLines` of a match.
+    ///
+    /// Line numbers are computed relative to the line that contains the
+    /// match. If that line cannot be found after the doc comment, or it would
+    /// put the first line before the start of the file, the lines cannot be
+    /// numbered and `None` is returned.
+    pub fn parse(peek_lines: &'a str, line_match: &LineMatch) -> Option<Self> {
+        let texts: Vec<_> = peek_lines.lines().collect();
+        let doc_len = doc_comment_len(&texts);
+
+        let needle = line_match.line.trim();
+        if needle.is_empty() {
+            return None;
+        }
+
+        let (match_index, _) = texts
+            .iter()
+            .enumerate()
+            .skip(doc_len)
+            .find(|(_, text)| text.trim_start().starts_with(needle))?;
+
+        // Line numbers start at 1.
+        if match_index >= line_match.number {
+            return None;
+        }
+
+        let first = line_match.number - match_index;
+
+        Some(PeekLines {
+            lines: texts
+                .into_iter()
+                .enumerate()
+                .map(|(idx, text)| PeekLine {
+                    number: first + idx,
+                    text,
+                })
+                .collect(),
+            doc_len,
+            match_index,
+        })
+    }
+
+    /// All of the lines.
+    pub fn lines(&self) -> &[PeekLine<'a>] {
+        &self.lines
+    }
+
+    /// The lines of the leading doc comment.
+    pub fn doc_comment(&self) -> &[PeekLine<'a>] {
+        &self.lines[..self.doc_len]
+    }
+
+    /// The lines following the doc comment, e.g., the declaration.
+    pub fn declaration(&self) -> &[PeekLine<'a>] {
+        &self.lines[self.doc_len..]
+    }
+
+    /// The index into [`lines`](#method.lines) of the line containing the
+    /// match.
+    pub fn match_index(&self) -> usize {
+        self.match_index
+    }
+
+    /// The line containing the match.
+    pub fn match_line(&self) -> &PeekLine<'a> {
+        &self.lines[self.match_index]
+    }
+
+    /// The text of the doc comment with the comment markers removed.
+    ///
+    /// Lines are joined with newlines and blank lines at the start and end are
+    /// removed.
+    pub fn doc_text(&self) -> String {
+        let lines: Vec<_> = self
+            .doc_comment()
+            .iter()
+            .map(|line| strip_comment(line.text))
+            .collect();
+
+        let start = lines.iter().position(|l| !l.is_empty()).unwrap_or(0);
+        let end = lines
+            .iter()
+            .rposition(|l| !l.is_empty())
+            .map_or(start, |idx| idx + 1);
+
+        lines[start..end].join("\n")
+    }
+}
+
+impl LineMatch {
+    /// Parse the [`peek_lines`](#structfield.peek_lines) of the match.
+    ///
+    /// This is `None` if there are no peek lines or they cannot be numbered
+    /// (see [`PeekLines::parse`](struct.PeekLines.html#method.parse)).
+    pub fn peek(&self) -> Option<PeekLines<'_>> {
+        self.peek_lines
+            .as_ref()
+            .and_then(|peek_lines| PeekLines::parse(peek_lines, self))
+    }
+}
+
+/// The number of lines of comments at the start of `texts`.
+///
+/// Lines starting with `*` are only comments inside a `/*` block, so that
+/// e.g. a dereference is not mistaken for one.
+fn doc_comment_len(texts: &[&str]) -> usize {
+    let mut in_block = false;
+
+    for (idx, text) in texts.iter().enumerate() {
+        let text = text.trim();
+
+        if in_block {
+            in_block = !text.contains("*/");
+        } else if let Some(rest) = text.strip_prefix("/*") {
+            in_block = !rest.contains("*/");
+        } else if !text.starts_with("//") {
+            return idx;
+        }
+    }
+
+    texts.len()
+}
+
+fn strip_comment(line: &str) -> &str {
+    let line = line.trim();
+    let mut line = line.strip_suffix("*/").unwrap_or(line);
+
+    for prefix in &["/**", "/*!", "/*", "///", "//!", "//", "*"] {
+        if let Some(rest) = line.strip_prefix(prefix) {
+            line = rest;
+            break;
+        }
+    }
+
+    line.trim()
+}
@@ -0,0 +1,198 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::error::Error;
+use std::fmt;
+
+use reqwest::Url;
+
+use crate::api::repo::Repo;
+
+/// The minimum length of a query, in characters.
+pub const MIN_QUERY_LENGTH: usize = 3;
+
+static TRUE: &str = "true";
+static FALSE: &str = "false";
+
+fn bool_to_str(b: bool) -> &'static str {
+    if b {
+        TRUE
+    } else {
+        FALSE
+    }
+}
+
+/// A validated search query.
+///
+/// Queries are created with a [`SearchQueryBuilder`](struct.SearchQueryBuilder.html).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SearchQuery {
+    query: String,
+    case_sensitive: bool,
+    regex: bool,
+    path: String,
+    repository: Repo,
+}
+
+impl SearchQuery {
+    /// Begin building a query for the given text.
+    pub fn builder<S: Into<String>>(query: S) -> SearchQueryBuilder {
+        SearchQueryBuilder {
+            query: SearchQuery {
+                query: query.into(),
+                case_sensitive: false,
+                regex: false,
+                path: String::new(),
+                repository: Repo::default(),
+            },
+        }
+    }
+
+    /// The text to search for.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Whether or not the search is case-sensitive.
+    pub fn case_sensitive(&self) -> bool {
+        self.case_sensitive
+    }
+
+    /// Whether or not the query is a regular expression.
+    pub fn regex(&self) -> bool {
+        self.regex
+    }
+
+    /// The path that the search is limited to.
+    ///
+    /// If empty, the search is not limited to any path.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The repository to search.
+    pub fn repository(&self) -> &Repo {
+        &self.repository
+    }
+
+    /// Build the URL for this query on the Searchfox instance at `base_url`.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `base_url` cannot be a base URL (e.g., a `data:`
+    /// URL).
+    pub fn url(&self, base_url: &Url) -> Url {
+        let mut url = base_url.clone();
+
+        url.path_segments_mut()
+            .expect("base_url cannot be a base URL")
+            .pop_if_empty()
+            .push(self.repository.name())
+            .push("search");
+
+        url.query_pairs_mut()
+            .append_pair("q", &self.query)
+            .append_pair("case", bool_to_str(self.case_sensitive))
+            .append_pair("regex", bool_to_str(self.regex))
+            .append_pair("path", &self.path);
+
+        url
+    }
+}
+
+/// A builder for a [`SearchQuery`](struct.SearchQuery.html).
+#[derive(Clone, Debug)]
+pub struct SearchQueryBuilder {
+    query: SearchQuery,
+}
+
+impl SearchQueryBuilder {
+    /// Set whether or not the search is case-sensitive.
+    ///
+    /// Searches are case-insensitive by default.
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.query.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Set whether or not the query is a regular expression.
+    pub fn regex(mut self, regex: bool) -> Self {
+        self.query.regex = regex;
+        self
+    }
+
+    /// Limit the search to paths matching the given path.
+    pub fn path<S: Into<String>>(mut self, path: S) -> Self {
+        self.query.path = path.into();
+        self
+    }
+
+    /// Set the repository to search.
+    ///
+    /// The default repository is `mozilla-central`.
+    pub fn repository(mut self, repository: Repo) -> Self {
+        self.query.repository = repository;
+        self
+    }
+
+    /// Validate and build the query.
+    pub fn build(self) -> Result<SearchQuery, QueryError> {
+        let query = self.query;
+
+        if query.query.chars().count() < MIN_QUERY_LENGTH {
+            return Err(QueryError::TooShort(query.query));
+        }
+
+        if query.repository.name().is_empty() {
+            return Err(QueryError::EmptyRepository);
+        }
+
+        if query.regex {
+            if let Err(e) = regex::Regex::new(&query.query) {
+                return Err(QueryError::InvalidRegex(e));
+            }
+        }
+
+        Ok(query)
+    }
+}
+
+/// An error that occurs when building an invalid query.
+#[derive(Debug)]
+pub enum QueryError {
+    /// The query was shorter than [`MIN_QUERY_LENGTH`](constant.MIN_QUERY_LENGTH.html).
+    TooShort(String),
+
+    /// No repository was given.
+    EmptyRepository,
+
+    /// The query was not a valid regular expression.
+    InvalidRegex(regex::Error),
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QueryError::TooShort(query) => write!(
+                f,
+                "query `{}' is too short: queries must be at least {} characters",
+                query, MIN_QUERY_LENGTH
+            ),
+            QueryError::EmptyRepository => write!(f, "no repository was given"),
+            QueryError::InvalidRegex(e) => write!(f, "invalid regular expression: {}", e),
+        }
+    }
+}
+
+impl Error for QueryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            QueryError::InvalidRegex(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,278 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+macro_rules! repo {
+    ( $($(#[$attr:meta])* $variant:ident => $default_name:expr $(,$name:expr)*);+ $(;)? ) => {
+        /// A repository indexed by Searchfox.
+        ///
+        /// Repositories that are not known to this crate (e.g., those hosted by
+        /// a custom mozsearch instance) are represented by
+        /// [`Repo::Custom`](enum.Repo.html#variant.Custom) and can be
+        /// resolved by name with a [`RepoRegistry`](struct.RepoRegistry.html).
+        #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+        pub enum Repo {
+            $($(#[$attr])* $variant,)+
+
+            /// A repository that is not known to this crate.
+            Custom(String),
+        }
+
+        impl Repo {
+            /// The repositories known to this crate.
+            pub fn builtin() -> Vec<Repo> {
+                vec![$(Repo::$variant),+]
+            }
+
+            /// The canonical name of the repository.
+            ///
+            /// This is the name used in Searchfox URLs.
+            pub fn name(&self) -> &str {
+                match self {
+                    $(Repo::$variant => $default_name,)+
+                    Repo::Custom(name) => name,
+                }
+            }
+
+            /// Alternate names for the repository.
+            pub fn aliases(&self) -> &'static [&'static str] {
+                match self {
+                    $(Repo::$variant => &[$($name),*],)+
+                    Repo::Custom(..) => &[],
+                }
+            }
+
+            /// The names and aliases of all repositories known to this crate.
+            pub fn values() -> Vec<&'static str> {
+                vec![
+                    $(
+                        $default_name $(, $name)*
+                    ),+
+                ]
+            }
+        }
+    }
+}
+
+repo! {
+    #[default]
+    MozillaCentral => "mozilla-central", "central", "m-c", "mc";
+    MozillaBeta => "mozilla-beta", "beta";
+    MozillaRelease => "mozilla-release", "release";
+    MozillaEsr60 => "mozilla-esr60", "esr60";
+    MozillaEsr68 => "mozilla-esr68", "esr68";
+    MozillaMobile => "mozilla-mobile", "mobile";
+    CommCentral => "comm-central", "comm";
+    CommEsr60 => "comm-esr60";
+    CommEsr68 => "comm-esr68";
+    Nss => "nss";
+    WhatWgHtml => "whatwg-html", "what-wg-html", "what-wg", "what", "html";
+}
+
+impl fmt::Display for Repo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Repo {
+    type Err = UnknownRepoError;
+
+    /// Parse the name or alias of a repository known to this crate.
+    ///
+    /// Names are matched case-insensitively. Use a
+    /// [`RepoRegistry`](struct.RepoRegistry.html) to also resolve custom
+    /// repositories.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RepoRegistry::new().resolve(s)
+    }
+}
+
+/// An error that occurs when resolving an unknown repository name.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnknownRepoError {
+    /// The name that could not be resolved.
+    pub name: String,
+
+    /// The canonical names of the repositories that could have been resolved.
+    pub valid: Vec<String>,
+}
+
+impl fmt::Display for UnknownRepoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown repository `{}'; expected one of: {}",
+            self.name,
+            self.valid.join(", ")
+        )
+    }
+}
+
+impl Error for UnknownRepoError {}
+
+/// A set of repositories that can be resolved by name or alias.
+///
+/// A new registry contains all the repositories known to this crate. Further
+/// repositories (and aliases for existing repositories) can be registered
+/// directly or loaded from a TOML configuration file of the form:
+///
+/// ```toml
+/// [[repository]]
+/// name = "mozilla-esr78"
+/// aliases = ["esr78"]
+/// ```
+#[derive(Clone, Debug)]
+pub struct RepoRegistry {
+    entries: Vec<(Repo, Vec<String>)>,
+}
+
+impl RepoRegistry {
+    /// Create a registry containing the repositories known to this crate.
+    pub fn new() -> Self {
+        RepoRegistry {
+            entries: Repo::builtin()
+                .into_iter()
+                .map(|repo| {
+                    let aliases = repo.aliases().iter().map(|&a| a.into()).collect();
+                    (repo, aliases)
+                })
+                .collect(),
+        }
+    }
+
+    /// Register a repository with the given name and aliases.
+    ///
+    /// If the name refers to an already registered repository, the aliases are
+    /// added to that repository. Otherwise, a new
+    /// [`Repo::Custom`](enum.Repo.html#variant.Custom) repository is
+    /// registered.
+    pub fn register<S, I>(&mut self, name: &str, aliases: I) -> Repo
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = S>,
+    {
+        let aliases = aliases.into_iter().map(Into::into);
+
+        if let Some(idx) = self.position(name) {
+            let entry = &mut self.entries[idx];
+            entry.1.extend(aliases);
+            entry.0.clone()
+        } else {
+            let repo = Repo::Custom(name.into());
+            self.entries.push((repo.clone(), aliases.collect()));
+            repo
+        }
+    }
+
+    /// Resolve a repository by its name or one of its aliases.
+    ///
+    /// Names are matched case-insensitively.
+    pub fn resolve(&self, name: &str) -> Result<Repo, UnknownRepoError> {
+        match self.position(name) {
+            Some(idx) => Ok(self.entries[idx].0.clone()),
+            None => Err(UnknownRepoError {
+                name: name.into(),
+                valid: self.names().map(Into::into).collect(),
+            }),
+        }
+    }
+
+    /// The canonical names of all registered repositories.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(repo, _)| repo.name())
+    }
+
+    /// Register the repositories listed in a TOML configuration file.
+    pub fn load<P: AsRef<Path>>(&mut self, path: P) -> Result<(), RepoConfigError> {
+        let config = fs::read_to_string(path).map_err(RepoConfigError::Io)?;
+        self.load_str(&config)
+    }
+
+    /// Register the repositories listed in a TOML configuration string.
+    pub fn load_str(&mut self, config: &str) -> Result<(), RepoConfigError> {
+        let config: RepoConfig = toml::from_str(config).map_err(RepoConfigError::Parse)?;
+
+        for entry in config.repositories {
+            if entry.name.is_empty() {
+                return Err(RepoConfigError::EmptyName);
+            }
+
+            self.register(&entry.name, entry.aliases);
+        }
+
+        Ok(())
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.entries.iter().position(|(repo, aliases)| {
+            repo.name().eq_ignore_ascii_case(name)
+                || aliases.iter().any(|a| a.eq_ignore_ascii_case(name))
+        })
+    }
+}
+
+impl Default for RepoRegistry {
+    fn default() -> Self {
+        RepoRegistry::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RepoConfig {
+    #[serde(default, rename = "repository")]
+    repositories: Vec<RepoConfigEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepoConfigEntry {
+    name: String,
+
+    #[serde(default)]
+    aliases: Vec<String>,
+}
+
+/// An error that occurs when loading a repository configuration file.
+#[derive(Debug)]
+pub enum RepoConfigError {
+    /// The configuration file could not be read.
+    Io(io::Error),
+
+    /// The configuration file was not valid.
+    Parse(toml::de::Error),
+
+    /// A repository in the configuration file had an empty name.
+    EmptyName,
+}
+
+impl fmt::Display for RepoConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RepoConfigError::Io(e) => write!(f, "could not read configuration: {}", e),
+            RepoConfigError::Parse(e) => write!(f, "invalid configuration: {}", e),
+            RepoConfigError::EmptyName => write!(f, "invalid configuration: empty repository name"),
+        }
+    }
+}
+
+impl Error for RepoConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RepoConfigError::Io(e) => Some(e),
+            RepoConfigError::Parse(e) => Some(e),
+            RepoConfigError::EmptyName => None,
+        }
+    }
+}
@@ -5,14 +5,22 @@
 // or http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use std::cmp;
 use std::collections::HashMap;
-use std::fmt;
+use std::io;
+use std::ops::Range;
+use std::str::FromStr;
 
-use serde::de::{Deserializer, Error, MapAccess, Unexpected, Visitor};
-use serde::Deserialize;
+use indexmap::IndexMap;
+use serde::de::Deserializer;
+use serde::ser::{SerializeMap, SerializeSeq, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::api::borrowed;
+use crate::error::{self, Error};
 
 /// A response from Searchfox.
-#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Response {
     /// The query that was searched for.
     #[serde(rename = "*title*")]
@@ -25,15 +33,58 @@ pub struct Response {
     pub timedout: bool,
 
     /// Matches for the query that occur in code.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub normal: Option<Matches>,
 
     /// Matches for the query that occur in test code.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub test: Option<Matches>,
 
     /// Matches for the query that occur in generated code.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub generated: Option<Matches>,
 }
 
+impl Response {
+    /// Parse a response from a slice of JSON.
+    pub fn from_slice(v: &[u8]) -> error::Result<Self> {
+        serde_json::from_slice(v).map_err(Error::decode)
+    }
+
+    /// Parse a response from a reader of JSON.
+    pub fn from_reader<R: io::Read>(rdr: R) -> error::Result<Self> {
+        serde_json::from_reader(rdr).map_err(Error::decode)
+    }
+
+    /// Reject responses that contain categories of results that are not
+    /// understood.
+    ///
+    /// Unknown categories are normally collected in
+    /// [`Matches::other`](struct.Matches.html#structfield.other). This is
+    /// useful for conformance testing against new versions of Searchfox.
+    pub fn into_strict(self) -> error::Result<Self> {
+        let key = [&self.normal, &self.test, &self.generated]
+            .iter()
+            .filter_map(|matches| matches.as_ref())
+            .filter_map(Matches::unknown_category)
+            .next();
+
+        match key {
+            Some(key) => Err(Error::UnknownCategory { url: None, key }),
+            None => Ok(self),
+        }
+    }
+}
+
+impl FromStr for Response {
+    type Err = Error;
+
+    /// Parse a response from a string of JSON.
+    fn from_str(s: &str) -> error::Result<Self> {
+        serde_json::from_str(s).map_err(Error::decode)
+    }
+}
+
 /// A collection of matches for a query.
 #[derive(Debug, Default, Eq, PartialEq)]
 pub struct Matches {
@@ -53,29 +104,47 @@ pub struct Matches {
 
     /// Code use that match the query.
     pub uses: FuzzyMatches,
+
+    /// Matches in categories that are not otherwise understood.
+    ///
+    /// Each entry is the kind of the category (e.g., `"IDL"`), the symbol that
+    /// was matched (which is empty if the category has no symbol), and the
+    /// matches themselves.
+    pub other: Vec<(String, String, FileMatches)>,
+
+    /// The keys of the categories of results (e.g., `"Uses (BrowserChild)"`)
+    /// in the order that Searchfox returned them.
+    ///
+    /// Categories are serialized in this order. Categories that are not
+    /// listed (e.g., because they were added after parsing) are serialized
+    /// after those that are, in a fixed order.
+    pub category_order: Vec<String>,
+}
+
+impl Matches {
+    /// The key of the first category of results that is not understood.
+    fn unknown_category(&self) -> Option<String> {
+        self.other
+            .first()
+            .map(|(kind, symbol, _)| category_key(kind, symbol))
+    }
 }
 
 /// Fuzzily found matches.
 ///
 /// When searching for a query, results may be returned that do not match the
 /// query exactly.
-pub type FuzzyMatches = HashMap<String, FileMatches>;
+///
+/// Symbols are in the order that Searchfox returned them.
+pub type FuzzyMatches = IndexMap<String, FileMatches>;
 
 /// A mapping of file names to query matches found within those files.
-pub type FileMatches = HashMap<String, Vec<LineMatch>>;
-
-/// A single match.
-// #[derive(Debug, Deserialize, Eq, PartialEq)]
-// pub struct FileMatches {
-//     /// The path that contained the matches.
-//     pub path: String,
-
-//     /// Individual matches in the file.
-//     pub lines: Vec<Match>,
-// }
+///
+/// Files are in the order that Searchfox returned them.
+pub type FileMatches = IndexMap<String, Vec<LineMatch>>;
 
 /// Context about a match.
-#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct MatchContext {
     /// The context where the match was found.
     ///
@@ -90,7 +159,7 @@ pub struct MatchContext {
 }
 
 /// A match for the query.
-#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Serialize)]
 pub struct LineMatch {
     /// The contents of the line.
     pub line: String,
@@ -100,142 +169,314 @@ pub struct LineMatch {
     pub number: usize,
 
     /// The position in the line where the symbol was found.
+    ///
+    /// These are byte offsets into `line`. Textual occurrences are found by
+    /// livegrep, which matches bytes, and mozsearch (`router/codesearch.py`)
+    /// passes its match bounds through unchanged. Semantic matches take their
+    /// bounds from the columns of the analysis records (`crossref` in
+    /// mozsearch's `tools`), which are byte columns for C++ and Rust, but may
+    /// not be for other languages, e.g., JavaScript columns come from
+    /// SpiderMonkey and count UTF-16 code units.
+    ///
+    /// Use [`match_range`](#method.match_range) or
+    /// [`matched_text`](#method.matched_text) to access the match safely.
     pub bounds: (usize, usize),
 
     /// Lines before and/or after the symbol that may provide some context.
     ///
     /// This will likely be a comment describing the match.
-    #[serde(
-        default,
-        rename = "peekLines",
-        deserialize_with = "deserialize_optional_string"
-    )]
+    #[serde(rename = "peekLines", skip_serializing_if = "Option::is_none")]
     pub peek_lines: Option<String>,
 
     /// Context for where the match was found.
     ///
     /// For example, this may be the name of the function where the match was
     /// found.
-    #[serde(flatten, default, deserialize_with = "deserialize_optional_context")]
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub context: Option<MatchContext>,
 }
 
+impl LineMatch {
+    /// The byte range of the match in `line`.
+    ///
+    /// Searchfox reports `bounds` as byte offsets into the original line, but
+    /// long lines are truncated, so the bounds may extend past the end of
+    /// `line`. The range is clamped to the line and widened to the nearest
+    /// character boundaries, so it is always safe to slice `line` with it.
+    pub fn match_range(&self) -> Range<usize> {
+        let len = self.line.len();
+        let mut start = cmp::min(self.bounds.0, len);
+        let mut end = cmp::min(cmp::max(self.bounds.0, self.bounds.1), len);
+
+        while !self.line.is_char_boundary(start) {
+            start -= 1;
+        }
+
+        while !self.line.is_char_boundary(end) {
+            end += 1;
+        }
+
+        start..end
+    }
+
+    /// The text of the line that matched the query.
+    pub fn matched_text(&self) -> &str {
+        &self.line[self.match_range()]
+    }
+
+    /// The text of the line before the match.
+    pub fn prefix(&self) -> &str {
+        &self.line[..self.match_range().start]
+    }
+
+    /// The text of the line after the match.
+    pub fn suffix(&self) -> &str {
+        &self.line[self.match_range().end..]
+    }
+}
+
+/// The matches in a single file, as they appear in a response.
 #[derive(Debug, Deserialize, Eq, PartialEq)]
-struct RawMatch {
-    lines: Vec<LineMatch>,
-    path: String,
+pub(crate) struct RawMatch {
+    pub(crate) lines: Vec<LineMatch>,
+    pub(crate) path: String,
+}
+
+/// Format the key of a category of results.
+pub(crate) fn category_key(kind: &str, symbol: &str) -> String {
+    if symbol.is_empty() {
+        kind.into()
+    } else {
+        format!("{} ({})", kind, symbol)
+    }
+}
+
+/// Split the key of a category of results into its kind and symbol.
+///
+/// Keys without a symbol (e.g., `"Files"`) have an empty symbol. Keys with an
+/// empty symbol (e.g., `"Definitions ()"`) are malformed and are kept whole as
+/// the kind, so that they are not mistaken for a known category.
+fn split_category_key(key: &str) -> (&str, &str) {
+    if let Some(inner) = key.strip_suffix(')') {
+        if let Some(idx) = inner.find(" (") {
+            let symbol = &inner[idx + " (".len()..];
+
+            if !symbol.is_empty() {
+                return (&inner[..idx], symbol);
+            }
+        }
+    }
+
+    (key, "")
+}
+
+/// The category of results that a key in a response refers to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Category<'a> {
+    Files,
+    Text,
+    Definitions(&'a str),
+    Declarations(&'a str),
+    Uses(&'a str),
+    Other(&'a str, &'a str),
+}
+
+impl<'a> Category<'a> {
+    /// Determine the category of results from its key.
+    pub(crate) fn of(key: &'a str) -> Self {
+        if key == "Files" {
+            return Category::Files;
+        } else if key == "Textual Occurrences" {
+            return Category::Text;
+        }
+
+        match split_category_key(key) {
+            ("Definitions", name) if !name.is_empty() => Category::Definitions(name),
+            ("Declarations", name) if !name.is_empty() => Category::Declarations(name),
+            ("Uses", name) if !name.is_empty() => Category::Uses(name),
+            (kind, symbol) => Category::Other(kind, symbol),
+        }
+    }
 }
 
-impl RawMatch {
-    fn into_hashmap(matches: Vec<RawMatch>) -> FileMatches {
-        matches.into_iter().map(|m| (m.path, m.lines)).collect()
+impl<'de> Deserialize<'de> for Matches {
+    /// Deserialize matches through their borrowed form, so that there is a
+    /// single implementation of the wire format.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        borrowed::Matches::deserialize(deserializer).map(borrowed::Matches::into_owned)
     }
+}
+
+impl<'de> Deserialize<'de> for LineMatch {
+    /// Deserialize a match through its borrowed form.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        borrowed::LineMatch::deserialize(deserializer).map(borrowed::LineMatch::into_owned)
+    }
+}
+
+impl Serialize for Matches {
+    /// Serialize the matches in the Searchfox wire format.
+    ///
+    /// Categories are written in the order of
+    /// [`category_order`](#structfield.category_order), followed by any
+    /// others in a fixed order: `Files`, `Textual Occurrences`,
+    /// `Definitions`, `Declarations`, `Uses`, and then any other categories.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut entries: Vec<(String, Vec<RawMatchRef>)> = vec![];
+
+        if !self.files.is_empty() {
+            let files = self
+                .files
+                .iter()
+                .map(|path| RawMatchRef {
+                    path,
+                    lines: &[],
+                    semantic: false,
+                })
+                .collect();
+            entries.push(("Files".into(), files));
+        }
 
-    fn into_paths(matches: Vec<RawMatch>) -> Vec<String> {
-        for m in &matches {
-            debug_assert!(m.lines.len() == 0);
+        if !self.text_matches.is_empty() {
+            entries.push((
+                "Textual Occurrences".into(),
+                RawMatchRef::from_file_matches(&self.text_matches, false),
+            ));
         }
 
-        matches.into_iter().map(|m| m.path).collect()
+        for (kind, fuzzy_matches) in &[
+            ("Definitions", &self.definitions),
+            ("Declarations", &self.declarations),
+            ("Uses", &self.uses),
+        ] {
+            for (name, file_matches) in fuzzy_matches.iter() {
+                entries.push((
+                    category_key(kind, name),
+                    RawMatchRef::from_file_matches(file_matches, true),
+                ));
+            }
+        }
+
+        for (kind, symbol, file_matches) in &self.other {
+            entries.push((
+                category_key(kind, symbol),
+                RawMatchRef::from_file_matches(file_matches, !symbol.is_empty()),
+            ));
+        }
+
+        if !self.category_order.is_empty() {
+            let mut positions = HashMap::new();
+            for (idx, key) in self.category_order.iter().enumerate() {
+                positions.entry(key.as_str()).or_insert(idx);
+            }
+
+            // The sort is stable, so unlisted categories keep their order.
+            entries
+                .sort_by_key(|(key, _)| positions.get(key.as_str()).cloned().unwrap_or(usize::MAX));
+        }
+
+        let mut map = serializer.serialize_map(Some(entries.len()))?;
+        for (key, value) in &entries {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
     }
 }
 
-static EXPECTED_FIELDS: &'static [&'static str] = &[
-    "Files",
-    "Textual Occurrences",
-    "Definitions (...)",
-    "Declarations (...)",
-    "Uses (...)",
-];
+/// The serialized form of a `RawMatch`.
+struct RawMatchRef<'a> {
+    path: &'a str,
+    lines: &'a [LineMatch],
+
+    /// Whether or not the lines are from a semantic (i.e., not textual) match.
+    ///
+    /// Searchfox always includes the context of semantic matches, even when it
+    /// is empty.
+    semantic: bool,
+}
 
-struct MatchesVisitor;
-impl<'de> Visitor<'de> for MatchesVisitor {
-    type Value = Matches;
+impl<'a> RawMatchRef<'a> {
+    fn from_file_matches(matches: &'a FileMatches, semantic: bool) -> Vec<Self> {
+        matches
+            .iter()
+            .map(|(path, lines)| RawMatchRef {
+                path,
+                lines,
+                semantic,
+            })
+            .collect()
+    }
+}
 
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("a map of matches")
+impl Serialize for RawMatchRef<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry(
+            "lines",
+            &LinesRef {
+                lines: self.lines,
+                semantic: self.semantic,
+            },
+        )?;
+        map.serialize_entry("path", self.path)?;
+        map.end()
     }
+}
+
+struct LinesRef<'a> {
+    lines: &'a [LineMatch],
+    semantic: bool,
+}
 
-    fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+impl Serialize for LinesRef<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
-        M: MapAccess<'de>,
+        S: Serializer,
     {
-        let mut matches = Matches::default();
-
-        while let Some((key, value)) = access.next_entry::<String, Vec<RawMatch>>()? {
-            if key == "Files" {
-                matches.files = RawMatch::into_paths(value);
-            } else if key == "Textual Occurrences" {
-                matches.text_matches = RawMatch::into_hashmap(value);
-            } else if key.ends_with(")") {
-                let key = &key[..key.len() - 1];
-
-                if key.starts_with("Definitions (") {
-                    let name = &key["Definitions (".len()..];
-
-                    matches
-                        .definitions
-                        .insert(name.into(), RawMatch::into_hashmap(value));
-                } else if key.starts_with("Declarations (") {
-                    let name = &key["Declarations (".len()..];
-                    matches
-                        .declarations
-                        .insert(name.into(), RawMatch::into_hashmap(value));
-                } else if key.starts_with("Uses (") {
-                    let name = &key["Uses (".len()..];
-                    matches
-                        .uses
-                        .insert(name.into(), RawMatch::into_hashmap(value));
-                } else {
-                    return Err(M::Error::unknown_field(&key, EXPECTED_FIELDS));
-                }
+        let mut seq = serializer.serialize_seq(Some(self.lines.len()))?;
+
+        for line in self.lines {
+            if self.semantic && line.context.is_none() {
+                seq.serialize_element(&SemanticLineMatchRef(line))?;
             } else {
-                return Err(M::Error::unknown_field(&key, EXPECTED_FIELDS));
+                seq.serialize_element(line)?;
             }
         }
 
-        Ok(matches)
+        seq.end()
     }
 }
 
-impl<'de> Deserialize<'de> for Matches {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+/// A semantic match without context, which is serialized with an empty context.
+struct SemanticLineMatchRef<'a>(&'a LineMatch);
+
+impl Serialize for SemanticLineMatchRef<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
-        D: Deserializer<'de>,
+        S: Serializer,
     {
-        deserializer.deserialize_map(MatchesVisitor)
-    }
-}
-
-/// Deserialize an `Option<String>` where the empty string is `None`.
-fn deserialize_optional_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    match <Option<String> as Deserialize>::deserialize(deserializer)? {
-        Some(ref s) if s.len() == 0 => Ok(None),
-        Some(s) => Ok(Some(s)),
-        None => Ok(None),
-    }
-}
-
-fn deserialize_optional_context<'de, D>(deserializer: D) -> Result<Option<MatchContext>, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    match <Option<MatchContext> as Deserialize>::deserialize(deserializer)? {
-        Some(ctx) => match (ctx.context.len(), ctx.symbol.len()) {
-            (0, 0) => Ok(None),
-            (0, _) => Err(D::Error::invalid_value(
-                Unexpected::Str(&ctx.symbol),
-                &"an empty symbol",
-            )),
-            (_, 0) => Err(D::Error::invalid_value(
-                Unexpected::Str(&ctx.symbol),
-                &"a non-empty symbol",
-            )),
-            (_, _) => Ok(Some(ctx)),
-        },
-        None => Ok(None),
+        let m = self.0;
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("bounds", &m.bounds)?;
+        map.serialize_entry("context", "")?;
+        map.serialize_entry("contextsym", "")?;
+        map.serialize_entry("line", &m.line)?;
+        map.serialize_entry("lno", &m.number)?;
+        if let Some(ref peek_lines) = m.peek_lines {
+            map.serialize_entry("peekLines", peek_lines)?;
+        }
+        map.end()
     }
 }
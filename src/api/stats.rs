@@ -0,0 +1,141 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::api::hit::Section;
+use crate::api::response::Response;
+
+/// Summary statistics about a response.
+///
+/// Every hit yielded by [`Response::hits`](struct.Response.html#method.hits)
+/// is counted, including file name matches. Keys are in the order they were
+/// first seen.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct ResponseStats {
+    /// The total number of hits and distinct files.
+    pub total: Counts,
+
+    /// Whether or not the request timed out.
+    ///
+    /// If true, the counts are for incomplete results.
+    pub timedout: bool,
+
+    /// Counts for each section.
+    pub sections: IndexMap<Section, Counts>,
+
+    /// Counts for each kind of category, e.g., `"Uses"`.
+    pub categories: IndexMap<String, Counts>,
+
+    /// Counts for each symbol that was matched.
+    pub symbols: IndexMap<String, Counts>,
+
+    /// Counts for each top-level directory.
+    ///
+    /// Files at the root of the repository are counted under the empty
+    /// string.
+    pub directories: IndexMap<String, Counts>,
+
+    /// Counts for each file extension, without the leading `.`.
+    ///
+    /// Files without an extension are counted under the empty string.
+    pub extensions: IndexMap<String, Counts>,
+}
+
+/// The number of hits and distinct files in a group of hits.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct Counts {
+    /// The number of hits.
+    pub hits: usize,
+
+    /// The number of distinct files with hits.
+    pub files: usize,
+}
+
+/// Accumulates the counts for groups of hits.
+struct Counter<'a, K> {
+    counts: IndexMap<K, Counts>,
+    seen: HashSet<(K, &'a str)>,
+}
+
+impl<'a, K: Clone + Eq + Hash> Counter<'a, K> {
+    fn new() -> Self {
+        Counter {
+            counts: IndexMap::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    fn add(&mut self, key: K, path: &'a str) {
+        let counts = self.counts.entry(key.clone()).or_default();
+        counts.hits += 1;
+
+        if self.seen.insert((key, path)) {
+            counts.files += 1;
+        }
+    }
+}
+
+impl Response {
+    /// Compute summary statistics about the response.
+    pub fn stats(&self) -> ResponseStats {
+        let mut total = Counter::new();
+        let mut sections = Counter::new();
+        let mut categories = Counter::new();
+        let mut symbols = Counter::new();
+        let mut directories = Counter::new();
+        let mut extensions = Counter::new();
+
+        for hit in self.hits() {
+            total.add((), hit.path);
+            sections.add(hit.section, hit.path);
+            categories.add(hit.kind.category_kind(), hit.path);
+            if let Some(symbol) = hit.symbol {
+                symbols.add(symbol, hit.path);
+            }
+            directories.add(top_level_directory(hit.path), hit.path);
+            extensions.add(extension(hit.path), hit.path);
+        }
+
+        ResponseStats {
+            total: total.counts.get(&()).cloned().unwrap_or_default(),
+            timedout: self.timedout,
+            sections: sections.counts,
+            categories: into_owned_keys(categories.counts),
+            symbols: into_owned_keys(symbols.counts),
+            directories: into_owned_keys(directories.counts),
+            extensions: into_owned_keys(extensions.counts),
+        }
+    }
+}
+
+fn into_owned_keys(counts: IndexMap<&str, Counts>) -> IndexMap<String, Counts> {
+    counts
+        .into_iter()
+        .map(|(key, counts)| (key.into(), counts))
+        .collect()
+}
+
+fn top_level_directory(path: &str) -> &str {
+    match path.find('/') {
+        Some(idx) => &path[..idx],
+        None => "",
+    }
+}
+
+fn extension(path: &str) -> &str {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+
+    match file_name.rfind('.') {
+        Some(idx) if idx > 0 => &file_name[idx + 1..],
+        _ => "",
+    }
+}
@@ -0,0 +1,281 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::fmt;
+use std::io;
+
+use serde::de::{
+    DeserializeSeed, Deserializer, Error as _, IgnoredAny, MapAccess, SeqAccess, Visitor,
+};
+
+use crate::api::hit::{Hit, HitKind, Section};
+use crate::api::response::{Category, RawMatch, Response};
+use crate::error::Error;
+
+/// The parts of a response that are not matches.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ResponseHeader {
+    /// The query that was searched for.
+    pub title: String,
+
+    /// Whether or not the request timed out.
+    ///
+    /// If true, the results will be incomplete.
+    pub timedout: bool,
+}
+
+impl Response {
+    /// Parse a response from a reader of JSON, calling `f` with each hit as
+    /// it is parsed.
+    ///
+    /// Unlike [`from_reader`](#method.from_reader), the response is never
+    /// held in memory all at once: only the matches for a single file are
+    /// buffered at a time. Hits are yielded in the order that they appear in
+    /// the response.
+    ///
+    /// The reader is read in many small pieces, so it should be buffered
+    /// (e.g., with a `BufReader`).
+    ///
+    /// If `f` returns an error, parsing stops and the error is returned. This
+    /// can be used to stop early. Errors parsing the response are converted
+    /// into the same error type.
+    ///
+    /// ```
+    /// # use searchfox_api::{Error, HitKind, Response};
+    /// # fn main() -> Result<(), Error> {
+    /// # let json = r#"{"*title*": "", "*timedout*": false}"#;
+    /// let mut uses = 0;
+    /// Response::for_each_hit(json.as_bytes(), |hit| {
+    ///     if hit.kind == HitKind::Use {
+    ///         uses += 1;
+    ///     }
+    ///     Ok::<_, Error>(())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn for_each_hit<R, F, E>(rdr: R, mut f: F) -> Result<ResponseHeader, E>
+    where
+        R: io::Read,
+        F: FnMut(Hit<'_>) -> Result<(), E>,
+        E: From<Error>,
+    {
+        let mut deserializer = serde_json::Deserializer::from_reader(rdr);
+        let mut stopped = None;
+
+        let result = deserializer
+            .deserialize_map(ResponseVisitor {
+                f: &mut f,
+                stopped: &mut stopped,
+            })
+            .and_then(|header| deserializer.end().map(|_| header));
+
+        match (result, stopped) {
+            (_, Some(e)) => Err(e),
+            (Ok(header), None) => Ok(header),
+            (Err(e), None) => Err(Error::decode(e).into()),
+        }
+    }
+}
+
+/// The message of the error used to abort parsing when the callback fails.
+///
+/// The callback's error is returned instead.
+static STOPPED: &str = "stopped by callback";
+
+struct ResponseVisitor<'f, F, E> {
+    f: &'f mut F,
+    stopped: &'f mut Option<E>,
+}
+
+impl<'de, F, E> Visitor<'de> for ResponseVisitor<'_, F, E>
+where
+    F: FnMut(Hit<'_>) -> Result<(), E>,
+{
+    type Value = ResponseHeader;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a search response")
+    }
+
+    fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut title = None;
+        let mut timedout = None;
+
+        while let Some(key) = access.next_key::<String>()? {
+            let section = match key.as_str() {
+                "*title*" => {
+                    title = Some(access.next_value()?);
+                    continue;
+                }
+                "*timedout*" => {
+                    timedout = Some(access.next_value()?);
+                    continue;
+                }
+                "normal" => Section::Normal,
+                "test" => Section::Test,
+                "generated" => Section::Generated,
+                _ => {
+                    access.next_value::<IgnoredAny>()?;
+                    continue;
+                }
+            };
+
+            access.next_value_seed(SectionSeed {
+                section,
+                f: &mut *self.f,
+                stopped: &mut *self.stopped,
+            })?;
+        }
+
+        Ok(ResponseHeader {
+            title: title.ok_or_else(|| M::Error::missing_field("*title*"))?,
+            timedout: timedout.ok_or_else(|| M::Error::missing_field("*timedout*"))?,
+        })
+    }
+}
+
+/// Yields the hits in a section of a response.
+struct SectionSeed<'f, F, E> {
+    section: Section,
+    f: &'f mut F,
+    stopped: &'f mut Option<E>,
+}
+
+impl<'de, F, E> DeserializeSeed<'de> for SectionSeed<'_, F, E>
+where
+    F: FnMut(Hit<'_>) -> Result<(), E>,
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_option(self)
+    }
+}
+
+impl<'de, F, E> Visitor<'de> for SectionSeed<'_, F, E>
+where
+    F: FnMut(Hit<'_>) -> Result<(), E>,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of matches")
+    }
+
+    fn visit_none<DE>(self) -> Result<Self::Value, DE> {
+        Ok(())
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+
+    fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        while let Some(key) = access.next_key::<String>()? {
+            let (kind, symbol) = match Category::of(&key) {
+                Category::Files => (HitKind::File, None),
+                Category::Text => (HitKind::Text, None),
+                Category::Definitions(name) => (HitKind::Definition, Some(name)),
+                Category::Declarations(name) => (HitKind::Declaration, Some(name)),
+                Category::Uses(name) => (HitKind::Use, Some(name)),
+                Category::Other(kind, symbol) => {
+                    (HitKind::Other(kind), Some(symbol).filter(|s| !s.is_empty()))
+                }
+            };
+
+            access.next_value_seed(CategorySeed {
+                section: self.section,
+                kind,
+                symbol,
+                f: &mut *self.f,
+                stopped: &mut *self.stopped,
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Yields the hits in a single category of results.
+struct CategorySeed<'a, 'f, F, E> {
+    section: Section,
+    kind: HitKind<'a>,
+    symbol: Option<&'a str>,
+    f: &'f mut F,
+    stopped: &'f mut Option<E>,
+}
+
+impl<'de, F, E> DeserializeSeed<'de> for CategorySeed<'_, '_, F, E>
+where
+    F: FnMut(Hit<'_>) -> Result<(), E>,
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, F, E> Visitor<'de> for CategorySeed<'_, '_, F, E>
+where
+    F: FnMut(Hit<'_>) -> Result<(), E>,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a list of matches")
+    }
+
+    fn visit_seq<S>(self, mut access: S) -> Result<Self::Value, S::Error>
+    where
+        S: SeqAccess<'de>,
+    {
+        while let Some(raw_match) = access.next_element::<RawMatch>()? {
+            let hit = Hit {
+                section: self.section,
+                kind: self.kind,
+                symbol: self.symbol,
+                path: &raw_match.path,
+                line: None,
+            };
+
+            let result = if self.kind == HitKind::File {
+                (self.f)(hit)
+            } else {
+                raw_match.lines.iter().try_for_each(|line| {
+                    (self.f)(Hit {
+                        line: Some(line),
+                        ..hit
+                    })
+                })
+            };
+
+            if let Err(e) = result {
+                *self.stopped = Some(e);
+                return Err(S::Error::custom(STOPPED));
+            }
+        }
+
+        Ok(())
+    }
+}
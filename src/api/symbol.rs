@@ -0,0 +1,166 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::fmt;
+
+use cpp_demangle::DemangleOptions;
+
+use crate::api::response::MatchContext;
+
+/// The language that a symbol belongs to.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Language {
+    /// C or C++.
+    Cpp,
+
+    /// JavaScript.
+    JavaScript,
+
+    /// A language that could not be determined.
+    Unknown,
+}
+
+/// The kind of a symbol.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SymbolKind {
+    /// A type, such as a class, struct, enum, or typedef (`T_`).
+    Type,
+
+    /// A namespace (`NS_`).
+    Namespace,
+
+    /// A field of a type (`F_`).
+    Field,
+
+    /// An enumeration constant (`E_`).
+    EnumConstant,
+
+    /// A preprocessor macro (`M_`).
+    Macro,
+
+    /// A variable or function local to a single file (`V_`).
+    Local,
+
+    /// A function or variable with an Itanium-mangled name (`_Z`).
+    Mangled,
+
+    /// A JavaScript property (`#`).
+    Property,
+
+    /// A symbol that is not otherwise understood.
+    Unknown,
+}
+
+/// A symbol generated by Searchfox.
+///
+/// Symbols are unique identifiers for the entities that Searchfox indexes.
+/// Their prefix determines what kind of entity they refer to.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Symbol<'a> {
+    raw: &'a str,
+    kind: SymbolKind,
+}
+
+static PREFIXES: &[(&str, SymbolKind)] = &[
+    ("_Z", SymbolKind::Mangled),
+    ("T_", SymbolKind::Type),
+    ("NS_", SymbolKind::Namespace),
+    ("F_", SymbolKind::Field),
+    ("E_", SymbolKind::EnumConstant),
+    ("M_", SymbolKind::Macro),
+    ("V_", SymbolKind::Local),
+    ("#", SymbolKind::Property),
+];
+
+impl<'a> Symbol<'a> {
+    /// Parse a single symbol.
+    pub fn parse(raw: &'a str) -> Self {
+        let kind = PREFIXES
+            .iter()
+            .find(|(prefix, _)| raw.starts_with(prefix))
+            .map_or(SymbolKind::Unknown, |&(_, kind)| kind);
+
+        Symbol { raw, kind }
+    }
+
+    /// Parse a comma-separated list of symbols.
+    ///
+    /// Searchfox lists multiple symbols for a context when, e.g., a method
+    /// overrides other methods. Empty entries are skipped.
+    pub fn parse_list(raw: &'a str) -> Vec<Self> {
+        raw.split(',')
+            .filter(|s| !s.is_empty())
+            .map(Symbol::parse)
+            .collect()
+    }
+
+    /// The symbol as generated by Searchfox.
+    pub fn raw(&self) -> &'a str {
+        self.raw
+    }
+
+    /// The kind of the symbol.
+    pub fn kind(&self) -> SymbolKind {
+        self.kind
+    }
+
+    /// The language of the symbol.
+    pub fn language(&self) -> Language {
+        match self.kind {
+            SymbolKind::Property => Language::JavaScript,
+            SymbolKind::Unknown => Language::Unknown,
+            _ => Language::Cpp,
+        }
+    }
+
+    /// The qualified name of the symbol, if it can be determined without
+    /// demangling.
+    ///
+    /// This is available for types, namespaces, and JavaScript properties.
+    pub fn name(&self) -> Option<&'a str> {
+        match self.kind {
+            SymbolKind::Type => Some(&self.raw["T_".len()..]),
+            SymbolKind::Namespace => Some(&self.raw["NS_".len()..]),
+            SymbolKind::Property => Some(&self.raw["#".len()..]),
+            _ => None,
+        }
+    }
+
+    /// Demangle an Itanium-mangled symbol into its full signature.
+    ///
+    /// The signature includes the namespaces, class, and name of the function,
+    /// as well as its parameter types and cv-qualifiers, e.g.,
+    /// `mozilla::dom::BrowserChild::GetFrom(nsIDocShell*)`.
+    ///
+    /// This returns `None` if the symbol is not mangled or cannot be demangled.
+    pub fn demangle(&self) -> Option<String> {
+        if self.kind != SymbolKind::Mangled {
+            return None;
+        }
+
+        cpp_demangle::Symbol::new(self.raw)
+            .ok()?
+            .demangle(&DemangleOptions::default())
+            .ok()
+    }
+}
+
+impl fmt::Display for Symbol<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.raw)
+    }
+}
+
+impl MatchContext {
+    /// Parse the symbols of the context.
+    ///
+    /// The first symbol is the context itself. Any further symbols are those
+    /// that the context overrides.
+    pub fn symbols(&self) -> Vec<Symbol<'_>> {
+        Symbol::parse_list(&self.symbol)
+    }
+}
@@ -0,0 +1,98 @@
+use std::borrow::Cow;
+
+use crate::api::borrowed;
+use crate::api::response::*;
+
+static RSP: &str = include_str!("../../tests/BrowserChild.json");
+
+macro_rules! assert_borrowed {
+    ($s:expr) => {
+        assert!(matches!($s, Cow::Borrowed(_)))
+    };
+}
+
+macro_rules! assert_owned {
+    ($s:expr) => {
+        assert!(matches!($s, Cow::Owned(_)))
+    };
+}
+
+#[test]
+fn test_into_owned() {
+    for json in &[RSP, include_str!("../../tests/Multibyte.json")] {
+        let rsp = borrowed::Response::from_str(json).unwrap();
+        let expected: Response = json.parse().unwrap();
+
+        assert_eq!(rsp.into_owned(), expected);
+    }
+}
+
+#[test]
+fn test_borrows() {
+    let rsp = borrowed::Response::from_slice(RSP.as_bytes()).unwrap();
+    assert_borrowed!(rsp.title);
+
+    let normal = rsp.normal.as_ref().unwrap();
+    let (symbol, file_matches) = normal.declarations.get_index(2).unwrap();
+    assert_eq!(symbol, "mozilla::dom::BrowserChild::BrowserChild");
+    assert_borrowed!(symbol);
+
+    let (path, lines) = file_matches.get_index(0).unwrap();
+    assert_eq!(path, "dom/ipc/BrowserChild.h");
+    assert_borrowed!(path);
+
+    let line = &lines[0];
+    assert_borrowed!(line.line);
+    assert_eq!(line.number, 189);
+
+    let context = line.context.as_ref().unwrap();
+    assert_borrowed!(context.context);
+    assert_borrowed!(context.symbol);
+
+    // Strings with escape sequences cannot be borrowed.
+    let peek_lines = line.peek_lines.as_ref().unwrap();
+    assert_owned!(peek_lines);
+    assert!(peek_lines.starts_with("/**\n"));
+
+    assert!(normal.files.iter().all(|f| matches!(f, Cow::Borrowed(_))));
+}
+
+#[test]
+fn test_escaped_key() {
+    let json = r#"{
+        "*timedout*": false,
+        "*title*": "operator\u003d",
+        "normal": {
+            "Uses (operator\u003d)": [
+                {
+                    "lines": [
+                        {
+                            "bounds": [6, 15],
+                            "context": "",
+                            "contextsym": "",
+                            "line": "a.operator=(b);",
+                            "lno": 3
+                        }
+                    ],
+                    "path": "a.cpp"
+                }
+            ],
+            "IDL (A)": []
+        }
+    }"#;
+
+    let rsp = borrowed::Response::from_str(json).unwrap();
+    assert_owned!(rsp.title);
+    assert_eq!(rsp.title, "operator=");
+
+    let normal = rsp.normal.as_ref().unwrap();
+    assert!(normal.uses.contains_key("operator="));
+    assert!(normal.uses["operator="]["a.cpp"][0].context.is_none());
+    assert_eq!(normal.other[0].0, "IDL");
+    assert_eq!(normal.other[0].1, "A");
+
+    assert!(borrowed::Response::from_str(json)
+        .unwrap()
+        .into_strict()
+        .is_err());
+}
@@ -0,0 +1,178 @@
+use serde_json::json;
+
+use crate::api::diff::*;
+use crate::api::hit::*;
+use crate::api::response::*;
+
+static RSP: &str = include_str!("../../tests/BrowserChild.json");
+
+fn changed() -> Response {
+    let mut rsp: Response = RSP.parse().unwrap();
+
+    let normal = rsp.normal.as_mut().unwrap();
+    normal.files.push("dom/ipc/BrowserParent.cpp".into());
+    normal.definitions.shift_remove("BrowserChildMap");
+    for line in &mut normal.uses["BrowserChild"]["accessible/generic/DocAccessible.cpp"] {
+        line.number += 2;
+    }
+
+    let test = rsp.test.as_mut().unwrap();
+    test.text_matches["testing/talos/talos/tests/cpstartup/extension/api.js"].push(LineMatch {
+        line: "    // Wait for the BrowserChild to be ready.".into(),
+        number: 90,
+        bounds: (19, 31),
+        peek_lines: None,
+        context: None,
+    });
+
+    rsp
+}
+
+#[test]
+fn test_diff_identical() {
+    let rsp: Response = RSP.parse().unwrap();
+    let diff = ResponseDiff::new(&rsp, &rsp);
+
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_string(), "");
+}
+
+#[test]
+fn test_diff() {
+    let old: Response = RSP.parse().unwrap();
+    let new = changed();
+    let diff = ResponseDiff::new(&old, &new);
+
+    assert_eq!(
+        diff.categories
+            .iter()
+            .map(|c| (c.section, c.kind.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            (Section::Normal, "Files"),
+            (Section::Normal, "Definitions"),
+            (Section::Normal, "Uses"),
+            (Section::Test, "Textual Occurrences"),
+        ]
+    );
+
+    let files = &diff.categories[0];
+    assert_eq!(files.added_files, vec!["dom/ipc/BrowserParent.cpp"]);
+    assert!(files.removed_files.is_empty());
+    assert!(files.added_hits.is_empty());
+
+    let definitions = &diff.categories[1];
+    assert_eq!(definitions.removed_symbols, vec!["BrowserChildMap"]);
+    assert_eq!(definitions.removed_files, vec!["dom/ipc/BrowserChild.cpp"]);
+    assert_eq!(
+        definitions.removed_hits,
+        vec![HitChange {
+            symbol: Some("BrowserChildMap".into()),
+            path: "dom/ipc/BrowserChild.cpp".into(),
+            number: 174,
+            line: "typedef nsDataHashtable<nsUint64HashKey, BrowserChild*> BrowserChildMap;".into(),
+        }]
+    );
+
+    let uses = &diff.categories[2];
+    assert!(uses.added_hits.is_empty());
+    assert!(uses.removed_hits.is_empty());
+    assert_eq!(
+        uses.moved_hits
+            .iter()
+            .map(|hit| (hit.old_number, hit.new_number))
+            .collect::<Vec<_>>(),
+        vec![(1379, 1381), (1380, 1382)]
+    );
+
+    let text = &diff.categories[3];
+    assert!(text.added_files.is_empty());
+    assert_eq!(text.added_hits.len(), 1);
+    assert_eq!(text.added_hits[0].number, 90);
+    assert_eq!(text.added_hits[0].symbol, None);
+
+    // Swapping the responses swaps the additions and removals.
+    let reversed = ResponseDiff::new(&new, &old);
+    assert_eq!(reversed.categories[0].removed_files, files.added_files);
+    assert_eq!(reversed.categories[1].added_hits, definitions.removed_hits);
+}
+
+#[test]
+fn test_diff_duplicate_lines() {
+    let line = |number| LineMatch {
+        line: "}".into(),
+        number,
+        bounds: (0, 1),
+        peek_lines: None,
+        context: None,
+    };
+    let response = |numbers: &[usize]| Response {
+        title: "}".into(),
+        timedout: false,
+        normal: Some(Matches {
+            text_matches: vec![("a.cpp".into(), numbers.iter().cloned().map(line).collect())]
+                .into_iter()
+                .collect(),
+            ..Matches::default()
+        }),
+        test: None,
+        generated: None,
+    };
+
+    let diff = ResponseDiff::new(&response(&[1, 5, 9]), &response(&[1, 7]));
+    let text = &diff.categories[0];
+
+    assert_eq!(text.moved_hits.len(), 1);
+    assert_eq!(text.moved_hits[0].old_number, 5);
+    assert_eq!(text.moved_hits[0].new_number, 7);
+    assert_eq!(text.removed_hits.len(), 1);
+    assert_eq!(text.removed_hits[0].number, 9);
+}
+
+#[test]
+fn test_diff_display() {
+    let diff = ResponseDiff::new(&RSP.parse().unwrap(), &changed());
+
+    assert_eq!(
+        diff.to_string(),
+        "normal: Files\n\
+         + file dom/ipc/BrowserParent.cpp\n\
+         \n\
+         normal: Definitions\n\
+         - symbol BrowserChildMap\n\
+         - file dom/ipc/BrowserChild.cpp\n\
+         - dom/ipc/BrowserChild.cpp:174: typedef nsDataHashtable<nsUint64HashKey, BrowserChild*> BrowserChildMap; [BrowserChildMap]\n\
+         \n\
+         normal: Uses\n\
+         ~ accessible/generic/DocAccessible.cpp:1379 -> 1381: if (RefPtr<dom::BrowserChild> browserChild = [BrowserChild]\n\
+         ~ accessible/generic/DocAccessible.cpp:1380 -> 1382: dom::BrowserChild::GetFrom(docShell)) { [BrowserChild]\n\
+         \n\
+         test: Textual Occurrences\n\
+         + testing/talos/talos/tests/cpstartup/extension/api.js:90: // Wait for the BrowserChild to be ready.\n"
+    );
+}
+
+#[test]
+fn test_diff_json() {
+    let diff = ResponseDiff::new(&RSP.parse().unwrap(), &changed());
+    let json = serde_json::to_value(&diff).unwrap();
+
+    assert_eq!(
+        json["categories"][0],
+        json!({
+            "section": "normal",
+            "kind": "Files",
+            "added_files": ["dom/ipc/BrowserParent.cpp"],
+        })
+    );
+    assert_eq!(
+        json["categories"][2]["moved_hits"][0],
+        json!({
+            "symbol": "BrowserChild",
+            "path": "accessible/generic/DocAccessible.cpp",
+            "old_number": 1379,
+            "new_number": 1381,
+            "line": "if (RefPtr<dom::BrowserChild> browserChild =",
+        })
+    );
+}
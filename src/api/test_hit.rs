@@ -0,0 +1,129 @@
+use crate::api::hit::*;
+use crate::api::response::*;
+
+static RSP: &str = include_str!("../../tests/BrowserChild.json");
+
+#[test]
+fn test_hits() {
+    let rsp: Response = serde_json::from_str(RSP).unwrap();
+    let hits: Vec<_> = rsp.hits().collect();
+
+    // 5 + 3 files, 23 line matches.
+    assert_eq!(hits.len(), 31);
+    assert_eq!(hits.iter().filter(|h| h.line.is_none()).count(), 8);
+
+    assert_eq!(
+        hits[0],
+        Hit {
+            section: Section::Normal,
+            kind: HitKind::File,
+            symbol: None,
+            path: "dom/base/InProcessBrowserChildMessageManager.cpp",
+            line: None,
+        }
+    );
+
+    let first_text =
+        &rsp.normal.as_ref().unwrap().text_matches["browser/modules/AsyncTabSwitcher.jsm"][0];
+    assert_eq!(
+        hits[5],
+        Hit {
+            section: Section::Normal,
+            kind: HitKind::Text,
+            symbol: None,
+            path: "browser/modules/AsyncTabSwitcher.jsm",
+            line: Some(first_text),
+        }
+    );
+
+    let last = hits.last().unwrap();
+    assert_eq!(last.section, Section::Generated);
+    assert_eq!(last.kind, HitKind::Use);
+    assert_eq!(last.symbol, Some("BrowserChild"));
+    assert_eq!(last.path, "__GENERATED__/ipc/ipdl/PBrowserChild.cpp");
+    assert_eq!(last.line.unwrap().number, 476);
+
+    // Sections are yielded in order.
+    let sections: Vec<_> = hits.iter().map(|h| h.section).collect();
+    let mut sorted = sections.clone();
+    sorted.sort();
+    assert_eq!(sections, sorted);
+}
+
+#[test]
+fn test_filters() {
+    let rsp: Response = serde_json::from_str(RSP).unwrap();
+
+    assert_eq!(rsp.hits().section(Section::Test).count(), 4);
+    assert_eq!(rsp.hits().section(Section::Generated).count(), 5);
+    assert_eq!(rsp.hits().kind(HitKind::Definition).count(), 4);
+    assert_eq!(rsp.hits().kind(HitKind::Declaration).count(), 5);
+
+    let paths: Vec<_> = rsp
+        .hits()
+        .section(Section::Normal)
+        .kind(HitKind::Use)
+        .path_prefix("accessible/")
+        .map(|h| (h.path, h.line.unwrap().number))
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            ("accessible/base/NotificationController.cpp", 926),
+            ("accessible/generic/DocAccessible.cpp", 1379),
+            ("accessible/generic/DocAccessible.cpp", 1380),
+        ]
+    );
+}
+
+#[test]
+fn test_other_hits() {
+    let rsp: Response = serde_json::from_str(
+        r#"{
+            "*timedout*": false,
+            "*title*": "nsIBrowserChild",
+            "test": {
+                "IDL (nsIBrowserChild)": [
+                    {
+                        "lines": [{"bounds": [10, 25], "line": "interface nsIBrowserChild", "lno": 14}],
+                        "path": "dom/interfaces/base/nsIBrowserChild.idl"
+                    }
+                ]
+            }
+        }"#,
+    )
+    .unwrap();
+
+    let hits: Vec<_> = rsp.hits().kind(HitKind::Other("IDL")).collect();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].section, Section::Test);
+    assert_eq!(hits[0].symbol, Some("nsIBrowserChild"));
+    assert_eq!(hits[0].kind.to_string(), "IDL");
+    assert_eq!(hits[0].category(), "IDL (nsIBrowserChild)");
+}
+
+#[test]
+fn test_category() {
+    let rsp: Response = serde_json::from_str(RSP).unwrap();
+    let mut categories: Vec<_> = rsp
+        .hits()
+        .section(Section::Normal)
+        .map(|hit| hit.category())
+        .collect();
+    categories.dedup();
+
+    assert_eq!(
+        categories,
+        vec![
+            "Files",
+            "Textual Occurrences",
+            "Definitions (BrowserChild)",
+            "Definitions (BrowserChildMap)",
+            "Declarations (BrowserChild)",
+            "Declarations (BrowserChildMessageManager)",
+            "Declarations (mozilla::dom::BrowserChild::BrowserChild)",
+            "Uses (BrowserChild)",
+            "Uses (BrowserChildMap)",
+        ]
+    );
+}
@@ -0,0 +1,291 @@
+use indexmap::indexmap;
+
+use crate::api::hit::*;
+use crate::api::merge::*;
+use crate::api::response::*;
+
+static RSP: &str = include_str!("../../tests/BrowserChild.json");
+
+fn line_match(number: usize, bounds: (usize, usize)) -> LineMatch {
+    LineMatch {
+        line: "BrowserChild::Init(mozIDOMWindowProxy* aParent)".into(),
+        number,
+        bounds,
+        peek_lines: None,
+        context: None,
+    }
+}
+
+fn response(title: &str, timedout: bool, normal: Matches) -> Response {
+    Response {
+        title: title.into(),
+        timedout,
+        normal: Some(normal),
+        test: None,
+        generated: None,
+    }
+}
+
+fn first() -> Response {
+    response(
+        "BrowserChild",
+        false,
+        Matches {
+            files: vec!["dom/ipc/BrowserChild.cpp".into()],
+            definitions: indexmap! {
+                "BrowserChild".into() => indexmap! {
+                    "dom/ipc/BrowserChild.h".into() => vec![line_match(149, (6, 18))],
+                },
+            },
+            uses: indexmap! {
+                "BrowserChild".into() => indexmap! {
+                    "dom/ipc/BrowserChild.cpp".into() => vec![line_match(500, (0, 12))],
+                },
+            },
+            ..Matches::default()
+        },
+    )
+}
+
+fn second() -> Response {
+    response(
+        "BrowserChild::Init",
+        true,
+        Matches {
+            files: vec![
+                "dom/ipc/BrowserChild.cpp".into(),
+                "dom/ipc/BrowserChild.h".into(),
+            ],
+            uses: indexmap! {
+                "BrowserChild".into() => indexmap! {
+                    "dom/ipc/BrowserChild.cpp".into() => vec![
+                        line_match(500, (0, 12)),
+                        line_match(500, (14, 18)),
+                    ],
+                },
+                "BrowserChild::Init".into() => indexmap! {
+                    "dom/ipc/ContentChild.cpp".into() => vec![line_match(900, (4, 22))],
+                },
+            },
+            other: vec![("IDL".into(), "".into(), indexmap! {})],
+            ..Matches::default()
+        },
+    )
+}
+
+#[test]
+fn test_merge() {
+    let mut rsp = first();
+    rsp.merge(second());
+
+    assert_eq!(rsp.title, "BrowserChild");
+    assert!(rsp.timedout);
+
+    let normal = rsp.normal.as_ref().unwrap();
+    assert_eq!(
+        normal.files,
+        vec!["dom/ipc/BrowserChild.cpp", "dom/ipc/BrowserChild.h"]
+    );
+    assert_eq!(normal.definitions.len(), 1);
+    assert_eq!(
+        normal.uses.keys().collect::<Vec<_>>(),
+        vec!["BrowserChild", "BrowserChild::Init"]
+    );
+    assert_eq!(
+        normal.uses["BrowserChild"]["dom/ipc/BrowserChild.cpp"]
+            .iter()
+            .map(|l| l.bounds)
+            .collect::<Vec<_>>(),
+        vec![(0, 12), (14, 18)]
+    );
+    assert_eq!(normal.other.len(), 1);
+}
+
+#[test]
+fn test_merge_idempotent() {
+    let mut rsp: Response = RSP.parse().unwrap();
+    rsp.merge(RSP.parse().unwrap());
+
+    assert_eq!(rsp, RSP.parse().unwrap());
+}
+
+#[test]
+fn test_merge_sections() {
+    let mut rsp: Response = RSP.parse().unwrap();
+    rsp.generated = None;
+
+    let mut other: Response = RSP.parse().unwrap();
+    other.normal = None;
+
+    rsp.merge(other);
+    assert_eq!(rsp, RSP.parse().unwrap());
+}
+
+#[test]
+fn test_merged_response() {
+    let merged: MergedResponse = vec![("BrowserChild", first()), ("BrowserChild::Init", second())]
+        .into_iter()
+        .collect();
+
+    assert_eq!(merged.queries(), &["BrowserChild", "BrowserChild::Init"]);
+
+    let rsp = merged.response().unwrap();
+    assert!(rsp.timedout);
+
+    let sources: Vec<_> = rsp
+        .hits()
+        .map(|hit| (hit.category(), hit.path, merged.sources(&hit)))
+        .collect();
+
+    assert_eq!(
+        sources,
+        vec![
+            (
+                "Files".into(),
+                "dom/ipc/BrowserChild.cpp",
+                vec!["BrowserChild", "BrowserChild::Init"],
+            ),
+            (
+                "Files".into(),
+                "dom/ipc/BrowserChild.h",
+                vec!["BrowserChild::Init"],
+            ),
+            (
+                "Definitions (BrowserChild)".into(),
+                "dom/ipc/BrowserChild.h",
+                vec!["BrowserChild"],
+            ),
+            (
+                "Uses (BrowserChild)".into(),
+                "dom/ipc/BrowserChild.cpp",
+                vec!["BrowserChild", "BrowserChild::Init"],
+            ),
+            (
+                "Uses (BrowserChild)".into(),
+                "dom/ipc/BrowserChild.cpp",
+                vec!["BrowserChild::Init"],
+            ),
+            (
+                "Uses (BrowserChild::Init)".into(),
+                "dom/ipc/ContentChild.cpp",
+                vec!["BrowserChild::Init"],
+            ),
+        ]
+    );
+
+    let other: Response = RSP.parse().unwrap();
+    let hit = other.hits().next().unwrap();
+    assert!(merged.sources(&hit).is_empty());
+    assert_eq!(hit.section, Section::Normal);
+}
+
+#[test]
+fn test_merged_response_same_title() {
+    // Searchfox gives a query and its regular expression variant the same
+    // title.
+    let mut regex = first();
+    regex.normal.as_mut().unwrap().files = vec!["dom/ipc/BrowserChildMap.h".into()];
+
+    let mut merged = MergedResponse::new();
+    merged.add("BrowserChild", first());
+    merged.add("BrowserChild (regex)", regex);
+
+    assert_eq!(merged.queries(), &["BrowserChild", "BrowserChild (regex)"]);
+
+    let rsp = merged.response().unwrap();
+    let sources: Vec<_> = rsp
+        .hits()
+        .kind(HitKind::File)
+        .map(|hit| (hit.path, merged.sources(&hit)))
+        .collect();
+
+    assert_eq!(
+        sources,
+        vec![
+            ("dom/ipc/BrowserChild.cpp", vec!["BrowserChild"]),
+            ("dom/ipc/BrowserChildMap.h", vec!["BrowserChild (regex)"]),
+        ]
+    );
+    assert_eq!(
+        merged.sources(&rsp.hits().kind(HitKind::Definition).next().unwrap()),
+        vec!["BrowserChild", "BrowserChild (regex)"]
+    );
+}
+
+#[test]
+fn test_merge_duplicates() {
+    let mut rsp = response(
+        "IDL",
+        false,
+        Matches {
+            other: vec![(
+                "IDL".into(),
+                "nsIBrowserChild".into(),
+                indexmap! {
+                    "dom/interfaces/base/nsIBrowserChild.idl".into() => vec![
+                        line_match(10, (0, 15)),
+                        line_match(10, (0, 15)),
+                    ],
+                },
+            )],
+            ..Matches::default()
+        },
+    );
+    let other = response(
+        "IDL",
+        false,
+        Matches {
+            other: vec![(
+                "IDL".into(),
+                "nsIBrowserChild".into(),
+                indexmap! {
+                    "dom/interfaces/base/nsIBrowserChild.idl".into() => vec![
+                        line_match(20, (0, 15)),
+                        line_match(10, (0, 15)),
+                        line_match(20, (0, 15)),
+                    ],
+                },
+            )],
+            ..Matches::default()
+        },
+    );
+
+    rsp.merge(other);
+
+    let normal = rsp.normal.unwrap();
+    assert_eq!(normal.other.len(), 1);
+    assert_eq!(
+        normal.other[0].2["dom/interfaces/base/nsIBrowserChild.idl"]
+            .iter()
+            .map(|l| l.number)
+            .collect::<Vec<_>>(),
+        vec![10, 20]
+    );
+}
+
+#[test]
+fn test_merge_category_order() {
+    let parse = |json: &str| -> Response { json.parse().unwrap() };
+
+    let mut rsp = parse(
+        r#"{"*title*": "WillRefresh", "*timedout*": false, "normal": {
+            "Uses (WillRefresh)": [],
+            "Definitions (WillRefresh)": []
+        }}"#,
+    );
+    rsp.merge(parse(
+        r#"{"*title*": "WillRefresh", "*timedout*": false, "normal": {
+            "Declarations (WillRefresh)": [],
+            "Uses (WillRefresh)": []
+        }}"#,
+    ));
+
+    assert_eq!(
+        rsp.normal.unwrap().category_order,
+        vec![
+            "Uses (WillRefresh)",
+            "Definitions (WillRefresh)",
+            "Declarations (WillRefresh)",
+        ]
+    );
+}
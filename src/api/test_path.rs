@@ -0,0 +1,154 @@
+use crate::api::hit::*;
+use crate::api::path::*;
+use crate::api::response::*;
+
+static RSP: &str = include_str!("../../tests/BrowserChild.json");
+
+#[test]
+fn test_classify() {
+    let cases = &[
+        ("dom/ipc/BrowserChild.h", Section::Normal, false, None),
+        (
+            "__GENERATED__/ipc/ipdl/PBrowserChild.cpp",
+            Section::Generated,
+            false,
+            None,
+        ),
+        (
+            "__GENERATED__/dom/bindings/test/TestBindingHeader.h",
+            Section::Generated,
+            false,
+            None,
+        ),
+        (
+            "toolkit/components/windowwatcher/test/browser_new_content_window_chromeflags.js",
+            Section::Test,
+            false,
+            None,
+        ),
+        (
+            "testing/talos/talos/tests/cpstartup/extension/api.js",
+            Section::Test,
+            false,
+            None,
+        ),
+        (
+            "js/src/jit-test/tests/basic/bug1.js",
+            Section::Test,
+            false,
+            None,
+        ),
+        ("netwerk/test/unit/test_http.js", Section::Test, false, None),
+        (
+            "xpcom/tests/gtest/TestStrings.cpp",
+            Section::Test,
+            false,
+            None,
+        ),
+        (
+            "dom/Tests/Mochitest/test_a.html",
+            Section::Test,
+            false,
+            None,
+        ),
+        (
+            "gfx/skia/skia/src/core/SkCanvas.cpp",
+            Section::Normal,
+            true,
+            None,
+        ),
+        (
+            "third_party/rust/serde/src/lib.rs",
+            Section::Normal,
+            true,
+            None,
+        ),
+        (
+            "media/webrtc/third_party/foo.c",
+            Section::Normal,
+            true,
+            None,
+        ),
+        ("security/nss/tests/ssl/ssl.sh", Section::Test, true, None),
+        (
+            "Third_Party/rust/serde/src/lib.rs",
+            Section::Normal,
+            true,
+            None,
+        ),
+        (
+            "media/webrtc/THIRD_PARTY/Tests/foo.c",
+            Section::Test,
+            true,
+            None,
+        ),
+        (
+            "GFX/Skia/skia/src/core/SkCanvas.cpp",
+            Section::Normal,
+            true,
+            None,
+        ),
+        (
+            "dom/interfaces/base/nsIBrowserChild.idl",
+            Section::Normal,
+            false,
+            Some(IdlKind::Xpidl),
+        ),
+        (
+            "dom/webidl/Window.webidl",
+            Section::Normal,
+            false,
+            Some(IdlKind::WebIdl),
+        ),
+        (
+            "dom/ipc/PBrowser.ipdl",
+            Section::Normal,
+            false,
+            Some(IdlKind::Ipdl),
+        ),
+        (
+            "dom/ipc/DOMTypes.ipdlh",
+            Section::Normal,
+            false,
+            Some(IdlKind::Ipdl),
+        ),
+        (
+            "dom/interfaces/base/nsIFoo.IDL",
+            Section::Normal,
+            false,
+            Some(IdlKind::Xpidl),
+        ),
+        ("Makefile", Section::Normal, false, None),
+        ("dom/latest/x.cpp", Section::Normal, false, None),
+        ("tools/contest/main.py", Section::Normal, false, None),
+        ("docs/community/index.rst", Section::Normal, false, None),
+        ("build/biggtest/run.sh", Section::Normal, false, None),
+        ("dom/base/test", Section::Normal, false, None),
+    ];
+
+    for &(path, section, third_party, idl) in cases {
+        let kind = PathKind::classify(path);
+
+        assert_eq!(kind.section(), section, "{}", path);
+        assert_eq!(
+            kind.is_generated(),
+            section == Section::Generated,
+            "{}",
+            path
+        );
+        assert_eq!(kind.is_third_party(), third_party, "{}", path);
+        assert_eq!(kind.idl(), idl, "{}", path);
+    }
+
+    let kind = PathKind::classify("__GENERATED__/dom/bindings/test/TestBindingHeader.h");
+    assert!(kind.is_test());
+}
+
+#[test]
+fn test_classify_consistent() {
+    let rsp: Response = RSP.parse().unwrap();
+
+    for hit in rsp.hits() {
+        assert_eq!(hit.path_kind().section(), hit.section, "{}", hit.path);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::api::peek::*;
+use crate::api::response::*;
+
+static RSP: &str = include_str!("../../tests/BrowserChild.json");
+
+fn line_match(line: &str, number: usize, peek_lines: &str) -> LineMatch {
+    LineMatch {
+        line: line.into(),
+        number,
+        bounds: (0, 0),
+        peek_lines: Some(peek_lines.into()),
+        context: None,
+    }
+}
+
+#[test]
+fn test_peek() {
+    let rsp: Response = serde_json::from_str(RSP).unwrap();
+    let normal = rsp.normal.as_ref().unwrap();
+
+    let line = &normal.declarations["mozilla::dom::BrowserChild::BrowserChild"]
+        ["dom/ipc/BrowserChild.h"][0];
+    let peek = line.peek().unwrap();
+
+    assert_eq!(peek.lines().len(), 6);
+    assert_eq!(peek.lines()[0].number, 186);
+    assert_eq!(peek.doc_comment().len(), 3);
+    assert_eq!(peek.doc_text(), "Create a new BrowserChild object.");
+    assert_eq!(peek.declaration().len(), 3);
+    assert_eq!(peek.match_index(), 3);
+    assert_eq!(
+        peek.match_line(),
+        &PeekLine {
+            number: 189,
+            text: "BrowserChild(ContentChild* aManager, const TabId& aTabId, TabGroup* aTabGroup,",
+        }
+    );
+    assert_eq!(
+        peek.declaration().last().unwrap(),
+        &PeekLine {
+            number: 191,
+            text: "             uint32_t aChromeFlags, bool aIsTopLevel);",
+        }
+    );
+
+    let line = &normal.definitions["BrowserChild"]["dom/ipc/BrowserChild.h"][0];
+    let peek = line.peek().unwrap();
+
+    assert_eq!(peek.lines()[0].number, 145);
+    assert_eq!(peek.match_line().number, 149);
+    assert_eq!(
+        peek.doc_text(),
+        "BrowserChild implements the child actor part of the PBrowser protocol. See\n\
+         PBrowser for more information."
+    );
+    assert_eq!(peek.declaration().len(), 15);
+
+    let line = &normal.uses["BrowserChild"]["accessible/base/NotificationController.cpp"][0];
+    assert!(line.peek().is_none());
+}
+
+#[test]
+fn test_peek_no_doc_comment() {
+    let line = line_match(
+        "void Foo(int aBar,",
+        10,
+        "void Foo(int aBar,\n         int aBaz);\n",
+    );
+    let peek = line.peek().unwrap();
+
+    assert!(peek.doc_comment().is_empty());
+    assert_eq!(peek.doc_text(), "");
+    assert_eq!(peek.match_index(), 0);
+    assert_eq!(
+        peek.lines().iter().map(|l| l.number).collect::<Vec<_>>(),
+        vec![10, 11]
+    );
+}
+
+#[test]
+fn test_peek_match_not_first() {
+    let line = line_match(
+        "Foo(int aBar);",
+        20,
+        "// Line comment.\n/// Doc comment.\ntemplate <typename T>\nFoo(int aBar);\n",
+    );
+    let peek = line.peek().unwrap();
+
+    assert_eq!(peek.doc_text(), "Line comment.\nDoc comment.");
+    assert_eq!(peek.match_index(), 3);
+    assert_eq!(peek.declaration()[0].number, 19);
+    assert_eq!(peek.lines()[0].number, 17);
+}
+
+#[test]
+fn test_peek_match_not_found() {
+    let line = line_match("something else", 5, "/* Comment. */\nint x;\n");
+    assert!(line.peek().is_none());
+
+    // The match cannot be before the first line of the file.
+    let line = line_match("Foo(int aBar);", 2, "// A.\n// B.\n// C.\nFoo(int aBar);\n");
+    assert!(line.peek().is_none());
+}
+
+#[test]
+fn test_peek_dereference() {
+    let line = line_match("return NS_OK;", 30, "*aResult = nullptr;\nreturn NS_OK;\n");
+    let peek = line.peek().unwrap();
+
+    assert!(peek.doc_comment().is_empty());
+    assert_eq!(peek.match_index(), 1);
+    assert_eq!(peek.lines()[0].number, 29);
+
+    let line = line_match(
+        "int x;",
+        12,
+        "/*\n * Comment.\n */\n*aResult = nullptr;\nint x;\n",
+    );
+    let peek = line.peek().unwrap();
+
+    assert_eq!(peek.doc_comment().len(), 3);
+    assert_eq!(peek.doc_text(), "Comment.");
+    assert_eq!(peek.declaration()[0].text, "*aResult = nullptr;");
+    assert_eq!(peek.match_line().number, 12);
+}
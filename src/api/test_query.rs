@@ -0,0 +1,81 @@
+use reqwest::Url;
+
+use crate::api::query::*;
+use crate::api::repo::Repo;
+
+#[test]
+fn test_url() {
+    let base_url = Url::parse("https://searchfox.org").unwrap();
+
+    let query = SearchQuery::builder("BrowserChild").build().unwrap();
+    assert_eq!(
+        query.url(&base_url).as_str(),
+        "https://searchfox.org/mozilla-central/search?q=BrowserChild&case=false&regex=false&path="
+    );
+
+    let query = SearchQuery::builder("Browser Child&case=true")
+        .case_sensitive(true)
+        .path("dom/ipc/")
+        .repository(Repo::CommCentral)
+        .build()
+        .unwrap();
+    assert_eq!(
+        query.url(&base_url).as_str(),
+        "https://searchfox.org/comm-central/search?q=Browser+Child%26case%3Dtrue&case=true&regex=false&path=dom%2Fipc%2F"
+    );
+
+    let query = SearchQuery::builder("^Browser(Child|Parent)$")
+        .regex(true)
+        .build()
+        .unwrap();
+    assert_eq!(
+        query.url(&base_url).as_str(),
+        "https://searchfox.org/mozilla-central/search?q=%5EBrowser%28Child%7CParent%29%24&case=false&regex=true&path="
+    );
+}
+
+#[test]
+fn test_url_base_path() {
+    let query = SearchQuery::builder("BrowserChild").build().unwrap();
+
+    for base_url in &[
+        "https://example.com/mozsearch",
+        "https://example.com/mozsearch/",
+    ] {
+        let base_url = Url::parse(base_url).unwrap();
+        assert!(query
+            .url(&base_url)
+            .as_str()
+            .starts_with("https://example.com/mozsearch/mozilla-central/search?"));
+    }
+}
+
+#[test]
+fn test_validate() {
+    match SearchQuery::builder("ab").build() {
+        Err(QueryError::TooShort(ref q)) if q == "ab" => (),
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    // The minimum length is in characters, not bytes.
+    match SearchQuery::builder("\u{e9}\u{e9}").build() {
+        Err(QueryError::TooShort(_)) => (),
+        r => panic!("unexpected result: {:?}", r),
+    }
+    assert!(SearchQuery::builder("\u{e9}\u{e9}\u{e9}").build().is_ok());
+
+    match SearchQuery::builder("BrowserChild")
+        .repository(Repo::Custom("".into()))
+        .build()
+    {
+        Err(QueryError::EmptyRepository) => (),
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    // Invalid regular expressions are only rejected in regex mode.
+    assert!(SearchQuery::builder("foo(bar").build().is_ok());
+    match SearchQuery::builder("foo(bar").regex(true).build() {
+        Err(QueryError::InvalidRegex(_)) => (),
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
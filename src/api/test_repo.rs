@@ -0,0 +1,89 @@
+use crate::api::repo::*;
+
+#[test]
+fn test_from_str() {
+    assert_eq!("mozilla-central".parse(), Ok(Repo::MozillaCentral));
+    assert_eq!("M-C".parse(), Ok(Repo::MozillaCentral));
+    assert_eq!("esr68".parse(), Ok(Repo::MozillaEsr68));
+    assert_eq!("what-wg-html".parse(), Ok(Repo::WhatWgHtml));
+
+    let err = "mozilla-esr78".parse::<Repo>().unwrap_err();
+    assert_eq!(err.name, "mozilla-esr78");
+    assert!(err.valid.iter().any(|name| name == "mozilla-central"));
+    assert!(err.to_string().contains("mozilla-central, "));
+}
+
+#[test]
+fn test_display() {
+    for repo in Repo::builtin() {
+        assert_eq!(repo.to_string().parse(), Ok(repo));
+    }
+
+    assert_eq!(Repo::Custom("kaios".into()).to_string(), "kaios");
+}
+
+#[test]
+fn test_register() {
+    let mut registry = RepoRegistry::new();
+
+    assert_eq!(
+        registry.register("mozilla-esr78", vec!["esr78"]),
+        Repo::Custom("mozilla-esr78".into())
+    );
+    assert_eq!(
+        registry.resolve("ESR78"),
+        Ok(Repo::Custom("mozilla-esr78".into()))
+    );
+
+    // Registering an existing repository adds aliases to it.
+    assert_eq!(
+        registry.register("m-c", vec!["trunk"]),
+        Repo::MozillaCentral
+    );
+    assert_eq!(registry.resolve("trunk"), Ok(Repo::MozillaCentral));
+
+    assert_eq!(
+        registry.names().filter(|&n| n == "mozilla-central").count(),
+        1
+    );
+    assert!(registry.names().any(|n| n == "mozilla-esr78"));
+}
+
+#[test]
+fn test_load() {
+    let mut registry = RepoRegistry::new();
+
+    registry
+        .load_str(
+            r#"
+            [[repository]]
+            name = "mozilla-esr78"
+            aliases = ["esr78"]
+
+            [[repository]]
+            name = "glean"
+            "#,
+        )
+        .unwrap();
+
+    assert_eq!(
+        registry.resolve("esr78"),
+        Ok(Repo::Custom("mozilla-esr78".into()))
+    );
+    assert_eq!(registry.resolve("glean"), Ok(Repo::Custom("glean".into())));
+
+    match registry.load_str("[[repository]]\nname = \"\"\n") {
+        Err(RepoConfigError::EmptyName) => (),
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    match registry.load_str("[[repository]]\naliases = []\n") {
+        Err(RepoConfigError::Parse(_)) => (),
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    match registry.load("/nonexistent/repos.toml") {
+        Err(RepoConfigError::Io(_)) => (),
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
@@ -1,9 +1,8 @@
-use maplit::hashmap;
-use serde_json;
+use indexmap::indexmap;
 
 use crate::api::response::*;
 
-static RSP: &'static str = include_str!("../../tests/BrowserChild.json");
+static RSP: &str = include_str!("../../tests/BrowserChild.json");
 
 #[test]
 fn test_parse() {
@@ -13,16 +12,16 @@ fn test_parse() {
         timedout: false,
         title: "BrowserChild".into(),
         generated: Some(Matches {
-            declarations: hashmap! {},
-            definitions: hashmap! {},
+            declarations: indexmap! {},
+            definitions: indexmap! {},
             files: vec![
                 "__GENERATED__/dist/include/nsIBrowserChild.h".into(),
                 "__GENERATED__/ipc/ipdl/PBrowserChild.cpp".into(),
                 "__GENERATED__/ipc/ipdl/_ipdlheaders/mozilla/dom/PBrowserChild.h".into(),
             ],
-            text_matches: hashmap! {},
-            uses: hashmap! {
-                "BrowserChild".into() => hashmap! {
+            text_matches: indexmap! {},
+            uses: indexmap! {
+                "BrowserChild".into() => indexmap! {
                     "__GENERATED__/ipc/ipdl/PBrowserChild.cpp".into() => vec![
                         LineMatch {
                             line: "return SendPDocAccessibleConstructor((static_cast<BrowserChild*>(this))->AllocPDocAccessibleChild(aP".into(),
@@ -47,10 +46,12 @@ fn test_parse() {
                     ],
                 },
             },
+            other: vec![],
+            category_order: vec!["Files".into(), "Uses (BrowserChild)".into()],
         }),
         normal: Some(Matches {
-            declarations: hashmap! {
-                "BrowserChild".into() => hashmap! {
+            declarations: indexmap! {
+                "BrowserChild".into() => indexmap! {
                     "dom/base/TabGroup.h".into() => vec![
                         LineMatch {
                             line: "class BrowserChild;".into(),
@@ -79,7 +80,7 @@ fn test_parse() {
                     ],
                 },
 
-                "BrowserChildMessageManager".into() => hashmap! {
+                "BrowserChildMessageManager".into() => indexmap! {
                     "dom/base/nsWrapperCache.h".into() => vec![
                         LineMatch {
                             line: "class BrowserChildMessageManager;".into(),
@@ -91,7 +92,7 @@ fn test_parse() {
                     ],
                 },
 
-                "mozilla::dom::BrowserChild::BrowserChild".into() => hashmap! {
+                "mozilla::dom::BrowserChild::BrowserChild".into() => indexmap! {
                     "dom/ipc/BrowserChild.h".into() => vec![
                         LineMatch {
                             line: "BrowserChild(ContentChild* aManager, const TabId& aTabId, TabGroup* aTabGroup,".into(),
@@ -106,8 +107,8 @@ fn test_parse() {
                     ],
                 },
             },
-            definitions: hashmap! {
-                "BrowserChild".into() => hashmap! {
+            definitions: indexmap! {
+                "BrowserChild".into() => indexmap! {
                     "dom/ipc/BrowserChild.h".into() => vec![
                         LineMatch {
                             line: "class BrowserChild final : public nsMessageManagerScriptExecutor,".into(),
@@ -143,7 +144,7 @@ fn test_parse() {
                     ],
                 },
 
-                "BrowserChildMap".into() => hashmap! {
+                "BrowserChildMap".into() => indexmap! {
                     "dom/ipc/BrowserChild.cpp".into() => vec![
                         LineMatch {
                             line: "typedef nsDataHashtable<nsUint64HashKey, BrowserChild*> BrowserChildMap;".into(),
@@ -162,7 +163,7 @@ fn test_parse() {
                 "dom/ipc/BrowserChild.cpp".into(),
                 "dom/ipc/BrowserChild.h".into(),
             ],
-            text_matches: hashmap! {
+            text_matches: indexmap! {
                 "browser/modules/AsyncTabSwitcher.jsm".into() => vec![
                     LineMatch {
                         line: "    // constructing BrowserChild's, layer trees, etc, by showing a blank".into(),
@@ -183,8 +184,8 @@ fn test_parse() {
                     },
                 ],
             },
-            uses: hashmap! {
-                "BrowserChild".into() => hashmap! {
+            uses: indexmap! {
+                "BrowserChild".into() => indexmap! {
                     "accessible/base/NotificationController.cpp".into() => vec![
                         LineMatch {
                             line: "static_cast<BrowserChild*>(browserChild.get())".into(),
@@ -233,7 +234,7 @@ fn test_parse() {
                     ],
                 },
 
-                "BrowserChildMap".into() => hashmap! {
+                "BrowserChildMap".into() => indexmap! {
                     "dom/ipc/BrowserChild.cpp".into() => vec![
                         LineMatch {
                             line: "static BrowserChildMap* sBrowserChildren;".into(),
@@ -256,12 +257,24 @@ fn test_parse() {
                     ],
                 },
             },
+            other: vec![],
+            category_order: vec![
+                "Declarations (BrowserChild)".into(),
+                "Declarations (BrowserChildMessageManager)".into(),
+                "Declarations (mozilla::dom::BrowserChild::BrowserChild)".into(),
+                "Definitions (BrowserChild)".into(),
+                "Definitions (BrowserChildMap)".into(),
+                "Files".into(),
+                "Textual Occurrences".into(),
+                "Uses (BrowserChild)".into(),
+                "Uses (BrowserChildMap)".into(),
+            ],
         }),
         test: Some(Matches {
-            definitions: hashmap! {},
-            declarations: hashmap! {},
+            definitions: indexmap! {},
+            declarations: indexmap! {},
             files: vec![],
-            text_matches: hashmap! {
+            text_matches: indexmap! {
                 "testing/talos/talos/tests/cpstartup/extension/api.js".into() => vec![
                     LineMatch {
                         line: "const MESSAGES = [\"CPStartup:Go\", \"Content:BrowserChildReady\"];".into(),
@@ -295,9 +308,232 @@ fn test_parse() {
                     },
                 ],
             },
-            uses: hashmap! {},
+            uses: indexmap! {},
+            other: vec![],
+            category_order: vec!["Textual Occurrences".into()],
         }),
     };
 
     assert_eq!(rsp, expected);
 }
+
+#[test]
+fn test_round_trip() {
+    let rsp: Response = serde_json::from_str(RSP).unwrap();
+
+    let expected: serde_json::Value = serde_json::from_str(RSP).unwrap();
+    assert_eq!(serde_json::to_value(&rsp).unwrap(), expected);
+
+    let serialized = serde_json::to_string(&rsp).unwrap();
+    assert_eq!(serde_json::from_str::<Response>(&serialized).unwrap(), rsp);
+}
+
+#[test]
+fn test_order() {
+    let rsp: Response = serde_json::from_str(RSP).unwrap();
+    let normal = rsp.normal.unwrap();
+
+    assert_eq!(
+        normal.declarations.keys().collect::<Vec<_>>(),
+        vec![
+            "BrowserChild",
+            "BrowserChildMessageManager",
+            "mozilla::dom::BrowserChild::BrowserChild",
+        ]
+    );
+    assert_eq!(
+        normal.declarations["BrowserChild"]
+            .keys()
+            .collect::<Vec<_>>(),
+        vec!["dom/base/TabGroup.h", "dom/base/nsContentUtils.h"]
+    );
+    assert_eq!(
+        normal.uses["BrowserChild"].keys().collect::<Vec<_>>(),
+        vec![
+            "accessible/base/NotificationController.cpp",
+            "accessible/generic/DocAccessible.cpp",
+            "dom/ipc/TabContext.cpp",
+        ]
+    );
+}
+
+#[test]
+fn test_order_round_trip() {
+    let json = r#"{
+        "*timedout*": false,
+        "*title*": "WillRefresh",
+        "normal": {
+            "Uses (WillRefresh)": [
+                {"lines": [], "path": "layout/base/nsRefreshDriver.cpp"},
+                {"lines": [], "path": "accessible/base/NotificationController.cpp"}
+            ],
+            "Definitions (WillRefresh)": [
+                {"lines": [], "path": "layout/base/nsRefreshObservers.h"},
+                {"lines": [], "path": "accessible/base/NotificationController.h"}
+            ]
+        }
+    }"#;
+
+    let rsp: Response = serde_json::from_str(json).unwrap();
+    let normal = rsp.normal.as_ref().unwrap();
+    assert_eq!(
+        normal.uses["WillRefresh"].keys().collect::<Vec<_>>(),
+        vec![
+            "layout/base/nsRefreshDriver.cpp",
+            "accessible/base/NotificationController.cpp",
+        ]
+    );
+
+    assert_eq!(
+        normal.category_order,
+        vec!["Uses (WillRefresh)", "Definitions (WillRefresh)"]
+    );
+
+    assert_eq!(
+        serde_json::to_string(&rsp).unwrap(),
+        concat!(
+            r#"{"*title*":"WillRefresh","*timedout*":false,"normal":{"#,
+            r#""Uses (WillRefresh)":["#,
+            r#"{"lines":[],"path":"layout/base/nsRefreshDriver.cpp"},"#,
+            r#"{"lines":[],"path":"accessible/base/NotificationController.cpp"}],"#,
+            r#""Definitions (WillRefresh)":["#,
+            r#"{"lines":[],"path":"layout/base/nsRefreshObservers.h"},"#,
+            r#"{"lines":[],"path":"accessible/base/NotificationController.h"}]}}"#,
+        )
+    );
+}
+
+#[test]
+fn test_order_unlisted() {
+    let mut rsp: Response = r#"{
+        "*timedout*": false,
+        "*title*": "WillRefresh",
+        "normal": {
+            "Uses (WillRefresh)": []
+        }
+    }"#
+    .parse()
+    .unwrap();
+
+    // Categories that were not parsed are written after those that were.
+    let normal = rsp.normal.as_mut().unwrap();
+    normal.files.push("layout/base/nsRefreshDriver.cpp".into());
+
+    assert_eq!(
+        serde_json::to_string(&rsp).unwrap(),
+        concat!(
+            r#"{"*title*":"WillRefresh","*timedout*":false,"normal":{"#,
+            r#""Uses (WillRefresh)":[],"#,
+            r#""Files":[{"lines":[],"path":"layout/base/nsRefreshDriver.cpp"}]}}"#,
+        )
+    );
+}
+
+#[test]
+fn test_other_categories() {
+    let json = r#"{
+        "*timedout*": false,
+        "*title*": "nsIBrowserChild",
+        "normal": {
+            "IDL (nsIBrowserChild)": [
+                {
+                    "lines": [
+                        {
+                            "bounds": [10, 25],
+                            "context": "",
+                            "contextsym": "",
+                            "line": "interface nsIBrowserChild : nsISupports",
+                            "lno": 14
+                        }
+                    ],
+                    "path": "dom/interfaces/base/nsIBrowserChild.idl"
+                }
+            ],
+            "Uses (nsIBrowserChild)": [],
+            "Conformance": [],
+            "Definitions ()": []
+        }
+    }"#;
+
+    let rsp: Response = serde_json::from_str(json).unwrap();
+    let normal = rsp.normal.as_ref().unwrap();
+
+    assert_eq!(normal.uses.len(), 1);
+    assert_eq!(
+        normal.other,
+        vec![
+            (
+                "IDL".into(),
+                "nsIBrowserChild".into(),
+                indexmap! {
+                    "dom/interfaces/base/nsIBrowserChild.idl".into() => vec![
+                        LineMatch {
+                            line: "interface nsIBrowserChild : nsISupports".into(),
+                            number: 14,
+                            bounds: (10, 25),
+                            peek_lines: None,
+                            context: None,
+                        },
+                    ],
+                },
+            ),
+            ("Conformance".into(), "".into(), indexmap! {}),
+            ("Definitions ()".into(), "".into(), indexmap! {}),
+        ]
+    );
+    assert!(normal.definitions.is_empty());
+
+    let expected: serde_json::Value = serde_json::from_str(json).unwrap();
+    assert_eq!(serde_json::to_value(&rsp).unwrap(), expected);
+}
+
+#[test]
+fn test_matched_text() {
+    // Multibyte.json is written by hand, with bounds as byte offsets as
+    // livegrep reports them.
+    let rsp: Response = serde_json::from_str(include_str!("../../tests/Multibyte.json")).unwrap();
+    let text_matches = &rsp.normal.as_ref().unwrap().text_matches;
+
+    let m = &text_matches["browser/locales/fr/BrowserChild.jsm"][0];
+    assert_eq!(m.prefix(), "    // Affiche \u{ab}");
+    assert_eq!(m.matched_text(), "BrowserChild");
+    assert_eq!(m.suffix(), "\u{bb} \u{e0} l'utilisateur.");
+
+    let m = &text_matches["devtools/client/shared/BrowserChild.js"][0];
+    assert_eq!(m.matched_text(), "BrowserChild");
+    assert_eq!(m.suffix(), " \u{2713}\";");
+
+    // Bounds that fall inside a character are widened to include it.
+    let m = &text_matches["devtools/client/shared/BrowserChild.js"][1];
+    assert_eq!(m.match_range(), 2..17);
+    assert_eq!(m.prefix(), "\u{e9}");
+    assert_eq!(m.matched_text(), "\u{2713}BrowserChild");
+    assert_eq!(m.suffix(), "");
+
+    let m = &text_matches["intl/docs/BrowserChild.cpp"][0];
+    assert_eq!(m.matched_text(), "BrowserChild");
+    assert!(m.prefix().ends_with("\u{30b3}\u{30e1}\u{30f3}\u{30c8}: "));
+
+    // Bounds past the end of a truncated line are clamped.
+    let m = &text_matches["intl/docs/BrowserChild.cpp"][1];
+    assert_eq!(m.match_range(), 87..95);
+    assert_eq!(m.matched_text(), "aSameTab");
+    assert_eq!(m.suffix(), "");
+}
+
+#[test]
+fn test_matched_text_invalid_bounds() {
+    let mut m = LineMatch {
+        line: "\u{1f98a}fox".into(),
+        number: 1,
+        bounds: (10, 2),
+        peek_lines: None,
+        context: None,
+    };
+    assert_eq!(m.matched_text(), "");
+    assert_eq!(m.prefix(), "\u{1f98a}fox");
+
+    m.bounds = (1, 2);
+    assert_eq!(m.matched_text(), "\u{1f98a}");
+    assert_eq!(m.suffix(), "fox");
+}
@@ -0,0 +1,112 @@
+use crate::api::hit::*;
+use crate::api::response::*;
+use crate::api::stats::*;
+
+static RSP: &str = include_str!("../../tests/BrowserChild.json");
+
+fn counts(hits: usize, files: usize) -> Counts {
+    Counts { hits, files }
+}
+
+#[test]
+fn test_stats() {
+    let rsp: Response = RSP.parse().unwrap();
+    let stats = rsp.stats();
+
+    assert_eq!(stats.total, counts(31, 20));
+    assert!(!stats.timedout);
+
+    assert_eq!(
+        stats.sections.into_iter().collect::<Vec<_>>(),
+        vec![
+            (Section::Normal, counts(22, 15)),
+            (Section::Test, counts(4, 2)),
+            (Section::Generated, counts(5, 3)),
+        ]
+    );
+
+    assert_eq!(
+        stats.categories.keys().collect::<Vec<_>>(),
+        vec![
+            "Files",
+            "Textual Occurrences",
+            "Definitions",
+            "Declarations",
+            "Uses"
+        ]
+    );
+    assert_eq!(stats.categories["Files"], counts(8, 8));
+    assert_eq!(stats.categories["Textual Occurrences"], counts(6, 4));
+    assert_eq!(stats.categories["Uses"], counts(8, 5));
+
+    assert_eq!(stats.symbols.len(), 4);
+    assert_eq!(stats.symbols["BrowserChild"], counts(12, 9));
+    assert_eq!(stats.symbols["BrowserChildMap"], counts(3, 1));
+
+    assert_eq!(stats.directories.len(), 8);
+    assert_eq!(stats.directories["dom"], counts(15, 9));
+    assert_eq!(stats.directories["accessible"], counts(3, 2));
+    assert_eq!(stats.directories["__GENERATED__"], counts(5, 3));
+
+    assert_eq!(stats.extensions.len(), 5);
+    assert_eq!(stats.extensions["h"], counts(12, 9));
+    assert_eq!(stats.extensions["cpp"], counts(12, 6));
+    assert_eq!(stats.extensions["js"], counts(5, 3));
+}
+
+#[test]
+fn test_stats_paths() {
+    let rsp = Response {
+        title: "moz".into(),
+        timedout: true,
+        normal: Some(Matches {
+            files: vec![
+                "Makefile".into(),
+                "mach".into(),
+                ".hgignore".into(),
+                "build/.gitignore".into(),
+                "moz.configure".into(),
+                "python/mozbuild/setup.py".into(),
+            ],
+            ..Matches::default()
+        }),
+        test: None,
+        generated: None,
+    };
+    let stats = rsp.stats();
+
+    assert!(stats.timedout);
+    assert!(stats.symbols.is_empty());
+    assert_eq!(
+        stats.directories.into_iter().collect::<Vec<_>>(),
+        vec![
+            ("".into(), counts(4, 4)),
+            ("build".into(), counts(1, 1)),
+            ("python".into(), counts(1, 1)),
+        ]
+    );
+    assert_eq!(
+        stats.extensions.into_iter().collect::<Vec<_>>(),
+        vec![
+            ("".into(), counts(4, 4)),
+            ("configure".into(), counts(1, 1)),
+            ("py".into(), counts(1, 1)),
+        ]
+    );
+}
+
+#[test]
+fn test_stats_empty() {
+    let rsp: Response = r#"{"*title*": "nothing", "*timedout*": false}"#.parse().unwrap();
+    assert_eq!(rsp.stats(), ResponseStats::default());
+}
+
+#[test]
+fn test_stats_json() {
+    let rsp: Response = RSP.parse().unwrap();
+    let json = serde_json::to_value(rsp.stats()).unwrap();
+
+    assert_eq!(json["total"]["hits"], 31);
+    assert_eq!(json["sections"]["test"]["files"], 2);
+    assert_eq!(json["extensions"]["idl"]["hits"], 1);
+}
@@ -0,0 +1,197 @@
+use crate::api::hit::*;
+use crate::api::response::*;
+use crate::api::stream::*;
+use crate::error::Error;
+
+static RSP: &str = include_str!("../../tests/BrowserChild.json");
+
+/// An owned summary of a hit.
+type HitSummary = (Section, String, Option<String>, String, Option<usize>);
+
+fn summarize(hit: Hit) -> HitSummary {
+    (
+        hit.section,
+        hit.kind.to_string(),
+        hit.symbol.map(Into::into),
+        hit.path.into(),
+        hit.line.map(|line| line.number),
+    )
+}
+
+/// The sections and categories of the fixture, in the order they appear.
+static ORDER: &[(Section, &str)] = &[
+    (Section::Generated, "Files"),
+    (Section::Generated, "Uses (BrowserChild)"),
+    (Section::Normal, "Declarations (BrowserChild)"),
+    (Section::Normal, "Declarations (BrowserChildMessageManager)"),
+    (
+        Section::Normal,
+        "Declarations (mozilla::dom::BrowserChild::BrowserChild)",
+    ),
+    (Section::Normal, "Definitions (BrowserChild)"),
+    (Section::Normal, "Definitions (BrowserChildMap)"),
+    (Section::Normal, "Files"),
+    (Section::Normal, "Textual Occurrences"),
+    (Section::Normal, "Uses (BrowserChild)"),
+    (Section::Normal, "Uses (BrowserChildMap)"),
+    (Section::Test, "Textual Occurrences"),
+];
+
+#[test]
+fn test_for_each_hit() {
+    let mut hits = vec![];
+    let header = Response::for_each_hit(RSP.as_bytes(), |hit| {
+        hits.push(summarize(hit));
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+
+    assert_eq!(
+        header,
+        ResponseHeader {
+            title: "BrowserChild".into(),
+            timedout: false,
+        }
+    );
+
+    // Hits are yielded in the order they appear in the response, rather than
+    // grouped by kind.
+    let rsp: Response = RSP.parse().unwrap();
+    let expected: Vec<_> = ORDER
+        .iter()
+        .flat_map(|&(section, category)| {
+            rsp.hits()
+                .section(section)
+                .filter(move |hit| hit.category() == category)
+        })
+        .map(summarize)
+        .collect();
+
+    assert_eq!(expected.len(), rsp.hits().count());
+    assert_eq!(hits, expected);
+}
+
+#[test]
+fn test_for_each_hit_lines() {
+    let mut count = 0;
+    Response::for_each_hit(RSP.as_bytes(), |hit| {
+        if hit.kind == HitKind::Declaration
+            && hit.symbol == Some("mozilla::dom::BrowserChild::BrowserChild")
+        {
+            let line = hit.line.unwrap();
+            assert_eq!(hit.path, "dom/ipc/BrowserChild.h");
+            assert_eq!(line.number, 189);
+            assert_eq!(
+                line.context.as_ref().unwrap().context,
+                "mozilla::dom::BrowserChild"
+            );
+            assert!(line.peek_lines.is_some());
+            count += 1;
+        }
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn test_for_each_hit_other() {
+    let json = r#"{
+        "*timedout*": true,
+        "*title*": "nsIBrowserChild",
+        "normal": {
+            "IDL (nsIBrowserChild)": [
+                {
+                    "lines": [
+                        {
+                            "bounds": [10, 25],
+                            "line": "interface nsIBrowserChild : nsISupports",
+                            "lno": 14
+                        }
+                    ],
+                    "path": "dom/interfaces/base/nsIBrowserChild.idl"
+                }
+            ]
+        },
+        "test": null,
+        "*extra*": {"ignored": [1, 2, 3]}
+    }"#;
+
+    let mut hits = vec![];
+    let header = Response::for_each_hit(json.as_bytes(), |hit| {
+        hits.push(summarize(hit));
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+
+    assert!(header.timedout);
+    assert_eq!(
+        hits,
+        vec![(
+            Section::Normal,
+            "IDL".into(),
+            Some("nsIBrowserChild".into()),
+            "dom/interfaces/base/nsIBrowserChild.idl".into(),
+            Some(14),
+        )]
+    );
+}
+
+#[test]
+fn test_for_each_hit_invalid() {
+    let mut count = 0;
+    let truncated = &RSP[..RSP.len() / 2];
+
+    match Response::for_each_hit(truncated.as_bytes(), |_| {
+        count += 1;
+        Ok(())
+    }) {
+        Err(Error::Decode { url: None, .. }) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    // Hits before the error are still yielded.
+    assert!(count > 0);
+
+    match Response::for_each_hit(&br#"{"*timedout*": false}"#[..], |_| Ok(())) {
+        Err(Error::Decode { url: None, .. }) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
+
+#[derive(Debug)]
+enum IndexError {
+    Full,
+    Response(Error),
+}
+
+impl From<Error> for IndexError {
+    fn from(e: Error) -> Self {
+        IndexError::Response(e)
+    }
+}
+
+#[test]
+fn test_for_each_hit_stop() {
+    let mut count = 0;
+    let result = Response::for_each_hit(RSP.as_bytes(), |_| {
+        if count == 3 {
+            return Err(IndexError::Full);
+        }
+        count += 1;
+        Ok(())
+    });
+
+    match result {
+        Err(IndexError::Full) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+    assert_eq!(count, 3);
+
+    let truncated = &RSP[..RSP.len() / 2];
+    match Response::for_each_hit(truncated.as_bytes(), |_| Ok(())) {
+        Err(IndexError::Response(Error::Decode { url: None, .. })) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
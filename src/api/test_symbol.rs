@@ -0,0 +1,152 @@
+use crate::api::response::*;
+use crate::api::symbol::*;
+
+static RSP: &str = include_str!("../../tests/BrowserChild.json");
+
+#[test]
+fn test_parse() {
+    let cases = &[
+        (
+            "T_mozilla::dom::BrowserChild",
+            SymbolKind::Type,
+            Language::Cpp,
+            Some("mozilla::dom::BrowserChild"),
+        ),
+        (
+            "NS_mozilla::dom",
+            SymbolKind::Namespace,
+            Language::Cpp,
+            Some("mozilla::dom"),
+        ),
+        (
+            "F_<T_nsIWidget>_mBounds",
+            SymbolKind::Field,
+            Language::Cpp,
+            None,
+        ),
+        (
+            "E_<T_mozilla::dom::TabId>_Invalid",
+            SymbolKind::EnumConstant,
+            Language::Cpp,
+            None,
+        ),
+        ("M_9a0c1b2d3e4f5a6b", SymbolKind::Macro, Language::Cpp, None),
+        (
+            "V_3c4f5e6d_sBrowserChildren",
+            SymbolKind::Local,
+            Language::Cpp,
+            None,
+        ),
+        (
+            "_ZN7mozilla3dom13PBrowserChild28SendPPluginWidgetConstructorEv",
+            SymbolKind::Mangled,
+            Language::Cpp,
+            None,
+        ),
+        (
+            "#getInterface",
+            SymbolKind::Property,
+            Language::JavaScript,
+            Some("getInterface"),
+        ),
+        (
+            "something_else",
+            SymbolKind::Unknown,
+            Language::Unknown,
+            None,
+        ),
+    ];
+
+    for &(raw, kind, language, name) in cases {
+        let symbol = Symbol::parse(raw);
+        assert_eq!(symbol.raw(), raw);
+        assert_eq!(symbol.to_string(), raw);
+        assert_eq!(symbol.kind(), kind, "{}", raw);
+        assert_eq!(symbol.language(), language, "{}", raw);
+        assert_eq!(symbol.name(), name, "{}", raw);
+    }
+}
+
+#[test]
+fn test_parse_list() {
+    assert_eq!(Symbol::parse_list(""), vec![]);
+    assert_eq!(
+        Symbol::parse_list("#foo,,#bar"),
+        vec![Symbol::parse("#foo"), Symbol::parse("#bar")]
+    );
+}
+
+#[test]
+fn test_context_symbols() {
+    let rsp: Response = serde_json::from_str(RSP).unwrap();
+    let line = &rsp.normal.as_ref().unwrap().uses["BrowserChild"]
+        ["accessible/base/NotificationController.cpp"][0];
+    let symbols = line.context.as_ref().unwrap().symbols();
+
+    assert_eq!(
+        symbols.iter().map(|s| s.raw()).collect::<Vec<_>>(),
+        vec![
+            "_ZN7mozilla4a11y22NotificationController11WillRefreshENS_9TimeStampE",
+            "_ZN18nsARefreshObserver11WillRefreshEN7mozilla9TimeStampE",
+        ]
+    );
+    assert!(symbols.iter().all(|s| s.kind() == SymbolKind::Mangled));
+
+    let line = &rsp.normal.as_ref().unwrap().definitions["BrowserChild"]["widget/nsIWidget.h"][0];
+    let symbols = line.context.as_ref().unwrap().symbols();
+    assert_eq!(symbols.len(), 1);
+    assert_eq!(symbols[0].kind(), SymbolKind::Type);
+    assert_eq!(symbols[0].name(), Some("nsIWidget"));
+}
+
+#[test]
+fn test_demangle() {
+    let cases = &[
+        (
+            "_ZN7mozilla3dom13PBrowserChild28SendPPluginWidgetConstructorEv",
+            "mozilla::dom::PBrowserChild::SendPPluginWidgetConstructor()",
+        ),
+        (
+            "_ZN7mozilla3dom13PBrowserChild29SendPDocAccessibleConstructorEPNS_4a11y19PDocAccessibleChildERKyRKjS8_",
+            "mozilla::dom::PBrowserChild::SendPDocAccessibleConstructor(mozilla::a11y::PDocAccessibleChild*, unsigned long long const&, unsigned int const&, unsigned int const&)",
+        ),
+        (
+            "_ZN7mozilla3dom22MaybeInvalidTabContextC1ERKNS0_13IPCTabContextE",
+            "mozilla::dom::MaybeInvalidTabContext::MaybeInvalidTabContext(mozilla::dom::IPCTabContext const&)",
+        ),
+        (
+            "_ZNK7mozilla3dom12BrowserChild9WebWidgetEv",
+            "mozilla::dom::BrowserChild::WebWidget() const",
+        ),
+    ];
+
+    for &(raw, expected) in cases {
+        assert_eq!(Symbol::parse(raw).demangle().as_deref(), Some(expected));
+    }
+
+    assert_eq!(Symbol::parse("T_nsIWidget").demangle(), None);
+    assert_eq!(Symbol::parse("_Zgarbage").demangle(), None);
+}
+
+#[test]
+fn test_demangle_overloads() {
+    let rsp: Response = serde_json::from_str(RSP).unwrap();
+    let line = &rsp.normal.as_ref().unwrap().uses["BrowserChild"]
+        ["accessible/base/NotificationController.cpp"][0];
+    let signatures: Vec<_> = line
+        .context
+        .as_ref()
+        .unwrap()
+        .symbols()
+        .iter()
+        .map(|s| s.demangle().unwrap())
+        .collect();
+
+    assert_eq!(
+        signatures,
+        vec![
+            "mozilla::a11y::NotificationController::WillRefresh(mozilla::TimeStamp)",
+            "nsARefreshObserver::WillRefresh(mozilla::TimeStamp)",
+        ]
+    );
+}
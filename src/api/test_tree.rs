@@ -0,0 +1,124 @@
+use crate::api::hit::*;
+use crate::api::response::*;
+use crate::api::stats::Counts;
+use crate::api::tree::*;
+
+static RSP: &str = include_str!("../../tests/BrowserChild.json");
+
+fn counts(hits: usize, files: usize) -> Counts {
+    Counts { hits, files }
+}
+
+#[test]
+fn test_tree() {
+    let rsp: Response = RSP.parse().unwrap();
+    let tree = rsp.tree();
+
+    assert_eq!(tree.root().counts(), counts(31, 20));
+    assert_eq!(tree.root().depth(), 0);
+    assert_eq!(
+        tree.root().children().map(|n| n.name()).collect::<Vec<_>>(),
+        vec![
+            "dom",
+            "browser",
+            "devtools",
+            "widget",
+            "accessible",
+            "testing",
+            "toolkit",
+            "__GENERATED__",
+        ]
+    );
+
+    let dom = tree.root().child("dom").unwrap();
+    assert_eq!(dom.counts(), counts(15, 9));
+    assert_eq!(dom.path(), "dom");
+    assert!(!dom.is_file());
+
+    let ipc = tree.get("dom/ipc").unwrap();
+    assert_eq!(ipc.name(), "ipc");
+    assert_eq!(ipc.depth(), 2);
+    assert_eq!(ipc.counts(), counts(8, 3));
+
+    let file = tree.get("dom/ipc/BrowserChild.h").unwrap();
+    assert!(file.is_file());
+    assert_eq!(file.path(), "dom/ipc/BrowserChild.h");
+    assert_eq!(file.counts(), counts(3, 1));
+    assert_eq!(
+        file.hits().iter().map(|hit| hit.kind).collect::<Vec<_>>(),
+        vec![HitKind::File, HitKind::Definition, HitKind::Declaration]
+    );
+
+    assert!(tree.get("dom/ipc/Missing.h").is_none());
+    assert!(tree.get("").is_none());
+}
+
+#[test]
+fn test_tree_from_hits() {
+    let rsp: Response = RSP.parse().unwrap();
+    let mut tree: PathTree = rsp.hits().kind(HitKind::Use).collect();
+    tree.sort_by_hits();
+
+    assert_eq!(tree.root().counts(), counts(8, 5));
+    assert_eq!(
+        tree.root()
+            .children()
+            .map(|n| (n.name(), n.counts().hits))
+            .collect::<Vec<_>>(),
+        vec![("accessible", 3), ("dom", 3), ("__GENERATED__", 2)]
+    );
+}
+
+#[test]
+fn test_tree_collapse() {
+    let rsp: Response = RSP.parse().unwrap();
+    let mut tree: PathTree = rsp.hits().path_prefix("dom/").collect();
+
+    let visible = |tree: &PathTree| -> Vec<String> {
+        tree.visible().iter().map(|n| n.path().to_owned()).collect()
+    };
+
+    tree.collapse_at(2);
+    assert_eq!(
+        visible(&tree),
+        vec!["dom", "dom/base", "dom/interfaces", "dom/ipc"]
+    );
+    assert!(tree.get("dom/ipc").unwrap().is_collapsed());
+
+    tree.collapse_at(1);
+    assert_eq!(visible(&tree), vec!["dom"]);
+
+    tree.expand_at(1);
+    tree.expand_at(2);
+    assert_eq!(visible(&tree).len(), 14);
+
+    tree.collapse_at(1);
+    tree.collapse_at(2);
+    tree.expand_all();
+    assert_eq!(visible(&tree).len(), 14);
+}
+
+#[test]
+fn test_tree_display() {
+    let rsp: Response = RSP.parse().unwrap();
+    let mut tree: PathTree = rsp.hits().path_prefix("dom/").collect();
+    tree.sort_by_hits();
+    tree.collapse_at(3);
+
+    assert_eq!(
+        tree.to_string(),
+        "dom/ (15 hits in 9 files)\n\
+         \x20 ipc/ (8 hits in 3 files)\n\
+         \x20   BrowserChild.cpp (4 hits)\n\
+         \x20   BrowserChild.h (3 hits)\n\
+         \x20   TabContext.cpp (1 hit)\n\
+         \x20 base/ (6 hits in 5 files)\n\
+         \x20   TabGroup.h (2 hits)\n\
+         \x20   InProcessBrowserChildMessageManager.cpp (1 hit)\n\
+         \x20   InProcessBrowserChildMessageManager.h (1 hit)\n\
+         \x20   nsContentUtils.h (1 hit)\n\
+         \x20   nsWrapperCache.h (1 hit)\n\
+         \x20 interfaces/ (1 hit in 1 file)\n\
+         \x20   base/... (1 hit in 1 file)\n"
+    );
+}
@@ -0,0 +1,286 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::fmt;
+use std::iter::FromIterator;
+
+use indexmap::IndexMap;
+
+use crate::api::hit::Hit;
+use crate::api::response::Response;
+use crate::api::stats::Counts;
+
+/// Hits grouped into a tree by the components of their paths.
+///
+/// Every node has the number of hits and distinct files beneath it. Nodes
+/// can be collapsed, hiding their children when the tree is displayed.
+///
+/// A tree can be built from any iterator of hits, e.g., to find which
+/// directories use a symbol the most:
+///
+/// ```
+/// # use searchfox_api::{HitKind, PathTree, Response};
+/// # fn main() -> searchfox_api::Result<()> {
+/// # let rsp: Response = r#"{"*title*": "", "*timedout*": false}"#.parse()?;
+/// let mut tree: PathTree = rsp.hits().kind(HitKind::Use).collect();
+/// tree.sort_by_hits();
+/// tree.collapse_at(1);
+/// println!("{}", tree);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PathTree<'a> {
+    root: TreeNode<'a>,
+}
+
+/// A directory or file in a [`PathTree`](struct.PathTree.html).
+#[derive(Clone, Debug, Default)]
+pub struct TreeNode<'a> {
+    name: &'a str,
+    path: &'a str,
+    depth: usize,
+    counts: Counts,
+    collapsed: bool,
+    hits: Vec<Hit<'a>>,
+    children: IndexMap<&'a str, TreeNode<'a>>,
+}
+
+impl<'a> PathTree<'a> {
+    /// The root of the tree.
+    ///
+    /// The root has an empty name and path and is at depth 0. Its counts are
+    /// the totals for the whole tree.
+    pub fn root(&self) -> &TreeNode<'a> {
+        &self.root
+    }
+
+    /// The node at the given path, if any.
+    ///
+    /// Paths do not have a trailing `/`.
+    pub fn get(&self, path: &str) -> Option<&TreeNode<'a>> {
+        path.split('/')
+            .try_fold(&self.root, |node, component| node.children.get(component))
+    }
+
+    /// Sort the children of every node by the number of hits beneath them,
+    /// from most to least.
+    ///
+    /// Nodes with the same number of hits keep their order.
+    pub fn sort_by_hits(&mut self) {
+        self.root.walk_mut(&mut |node| {
+            node.children
+                .sort_by(|_, a, _, b| b.counts.hits.cmp(&a.counts.hits))
+        });
+    }
+
+    /// Collapse every node at the given depth.
+    ///
+    /// Top-level directories are at depth 1.
+    pub fn collapse_at(&mut self, depth: usize) {
+        self.set_collapsed(depth, true);
+    }
+
+    /// Expand every node at the given depth.
+    pub fn expand_at(&mut self, depth: usize) {
+        self.set_collapsed(depth, false);
+    }
+
+    /// Expand every node.
+    pub fn expand_all(&mut self) {
+        self.root.walk_mut(&mut |node| node.collapsed = false);
+    }
+
+    fn set_collapsed(&mut self, depth: usize, collapsed: bool) {
+        self.root.walk_mut(&mut |node| {
+            if node.depth == depth {
+                node.collapsed = collapsed;
+            }
+        });
+    }
+
+    /// The nodes that are not hidden by a collapsed ancestor, in depth-first
+    /// order.
+    ///
+    /// The root is not included.
+    pub fn visible(&self) -> Vec<&TreeNode<'a>> {
+        let mut nodes = vec![];
+        self.root.visible(&mut nodes);
+        nodes
+    }
+
+    fn insert(&mut self, hit: Hit<'a>) {
+        let path = hit.path;
+        let mut node = &mut self.root;
+        let mut end = 0;
+
+        for component in path.split('/') {
+            end += component.len();
+
+            let depth = node.depth + 1;
+            node = node.children.entry(component).or_insert_with(|| TreeNode {
+                name: component,
+                path: &path[..end],
+                depth,
+                ..TreeNode::default()
+            });
+
+            end += 1;
+        }
+
+        node.hits.push(hit);
+    }
+}
+
+impl<'a> FromIterator<Hit<'a>> for PathTree<'a> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Hit<'a>>,
+    {
+        let mut tree = PathTree::default();
+
+        for hit in iter {
+            tree.insert(hit);
+        }
+
+        tree.root.count();
+        tree
+    }
+}
+
+impl<'a> TreeNode<'a> {
+    /// The last component of the node's path.
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The path of the node.
+    pub fn path(&self) -> &'a str {
+        self.path
+    }
+
+    /// The number of components in the node's path.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// The number of hits and distinct files beneath the node.
+    pub fn counts(&self) -> Counts {
+        self.counts
+    }
+
+    /// Whether or not the node is collapsed.
+    pub fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    /// Whether or not the node is a file, i.e., it has hits of its own.
+    pub fn is_file(&self) -> bool {
+        !self.hits.is_empty()
+    }
+
+    /// The hits in the file at the node's path.
+    pub fn hits(&self) -> &[Hit<'a>] {
+        &self.hits
+    }
+
+    /// The children of the node.
+    pub fn children(&self) -> impl Iterator<Item = &TreeNode<'a>> {
+        self.children.values()
+    }
+
+    /// The child with the given name, if any.
+    pub fn child(&self, name: &str) -> Option<&TreeNode<'a>> {
+        self.children.get(name)
+    }
+
+    /// Compute the counts for the node and its children.
+    fn count(&mut self) -> Counts {
+        let mut counts = Counts {
+            hits: self.hits.len(),
+            files: if self.is_file() { 1 } else { 0 },
+        };
+
+        for child in self.children.values_mut() {
+            let child_counts = child.count();
+            counts.hits += child_counts.hits;
+            counts.files += child_counts.files;
+        }
+
+        self.counts = counts;
+        counts
+    }
+
+    fn walk_mut<F: FnMut(&mut TreeNode<'a>)>(&mut self, f: &mut F) {
+        f(self);
+
+        for child in self.children.values_mut() {
+            child.walk_mut(f);
+        }
+    }
+
+    fn visible<'t>(&'t self, nodes: &mut Vec<&'t TreeNode<'a>>) {
+        for child in self.children.values() {
+            nodes.push(child);
+
+            if !child.collapsed {
+                child.visible(nodes);
+            }
+        }
+    }
+}
+
+impl Response {
+    /// Group every hit in the response into a tree by path.
+    pub fn tree(&self) -> PathTree<'_> {
+        self.hits().collect()
+    }
+}
+
+impl fmt::Display for PathTree<'_> {
+    /// Display the visible nodes of the tree, one per line.
+    ///
+    /// Directories have a trailing `/` and collapsed directories have a
+    /// trailing `/...`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for node in self.visible() {
+            let suffix = match (node.children.is_empty(), node.collapsed) {
+                (true, _) => "",
+                (false, false) => "/",
+                (false, true) => "/...",
+            };
+
+            write!(
+                f,
+                "{:indent$}{}{} ({} {}",
+                "",
+                node.name,
+                suffix,
+                node.counts.hits,
+                if node.counts.hits == 1 { "hit" } else { "hits" },
+                indent = 2 * (node.depth - 1),
+            )?;
+
+            if !node.children.is_empty() {
+                write!(
+                    f,
+                    " in {} {}",
+                    node.counts.files,
+                    if node.counts.files == 1 {
+                        "file"
+                    } else {
+                        "files"
+                    }
+                )?;
+            }
+
+            writeln!(f, ")")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,267 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use futures::Future;
+use reqwest::Url;
+
+use crate::api::query::SearchQuery;
+use crate::api::response::Response;
+use crate::error::{Error, Result};
+use crate::transport::{
+    AsyncReqwestTransport, AsyncTransport, HttpResponse, ReqwestTransport, Transport,
+};
+
+/// The URL of the public Searchfox instance.
+pub static DEFAULT_BASE_URL: &str = "https://searchfox.org";
+
+fn default_base_url() -> Url {
+    Url::parse(DEFAULT_BASE_URL).unwrap()
+}
+
+pub(crate) fn check_base_url(base_url: &Url) {
+    assert!(
+        !base_url.cannot_be_a_base(),
+        "{} cannot be used as a base URL",
+        base_url
+    );
+}
+
+/// Decode the response to a search request.
+///
+/// If Searchfox timed out and `reject_timed_out` is set, the partial results
+/// are returned as an [`Error::TimedOut`](enum.Error.html#variant.TimedOut).
+fn decode(url: Url, rsp: HttpResponse, strict: bool, reject_timed_out: bool) -> Result<Response> {
+    if !rsp.status.is_success() {
+        return Err(Error::Status {
+            url,
+            status: rsp.status,
+        });
+    }
+
+    let rsp =
+        Response::from_slice(&rsp.body).and_then(
+            |rsp| {
+                if strict {
+                    rsp.into_strict()
+                } else {
+                    Ok(rsp)
+                }
+            },
+        );
+
+    match rsp {
+        Ok(rsp) if reject_timed_out && rsp.timedout => Err(Error::TimedOut {
+            url,
+            partial: Box::new(rsp),
+        }),
+        Ok(rsp) => Ok(rsp),
+        Err(e) => Err(e.with_url(url)),
+    }
+}
+
+/// A blocking client for a Searchfox instance.
+#[derive(Clone, Debug)]
+pub struct Client<T = ReqwestTransport> {
+    base_url: Url,
+    transport: T,
+    strict: bool,
+    reject_timed_out: bool,
+}
+
+impl Client {
+    /// Create a new client for [searchfox.org](https://searchfox.org).
+    pub fn new() -> Self {
+        Client::with_base_url(default_base_url())
+    }
+
+    /// Create a new client for the Searchfox instance at the given URL.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `base_url` cannot be a base URL (e.g., a `data:`
+    /// URL).
+    pub fn with_base_url(base_url: Url) -> Self {
+        Client::with_transport(base_url, ReqwestTransport::new())
+    }
+}
+
+impl<T: Transport> Client<T> {
+    /// Create a new client for the Searchfox instance at the given URL that
+    /// issues requests with the given transport.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `base_url` cannot be a base URL (e.g., a `data:`
+    /// URL).
+    pub fn with_transport(base_url: Url, transport: T) -> Self {
+        check_base_url(&base_url);
+
+        Client {
+            base_url,
+            transport,
+            strict: false,
+            reject_timed_out: false,
+        }
+    }
+
+    /// Set whether or not responses are parsed in strict mode.
+    ///
+    /// In strict mode, responses containing categories of results that are not
+    /// understood are rejected with an
+    /// [`Error::UnknownCategory`](enum.Error.html#variant.UnknownCategory).
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Set whether or not responses that timed out are rejected.
+    ///
+    /// By default, when Searchfox times out the partial results are returned
+    /// with [`Response::timedout`](struct.Response.html#structfield.timedout)
+    /// set. When this is set, they are instead returned in an
+    /// [`Error::TimedOut`](enum.Error.html#variant.TimedOut), e.g., for retry
+    /// logic to branch on.
+    pub fn reject_timed_out(mut self, reject_timed_out: bool) -> Self {
+        self.reject_timed_out = reject_timed_out;
+        self
+    }
+
+    /// The URL of the Searchfox instance.
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// The transport used to issue requests.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Perform a search.
+    pub fn search(&self, query: &SearchQuery) -> Result<Response> {
+        self.get(query.url(&self.base_url))
+    }
+
+    /// Retrieve the search results at the given URL.
+    ///
+    /// The URL must point to the `/search` endpoint of a Searchfox repository.
+    pub fn get(&self, url: Url) -> Result<Response> {
+        match self.transport.get(&url) {
+            Ok(rsp) => decode(url, rsp, self.strict, self.reject_timed_out),
+            Err(e) => Err(Error::transport(url, e)),
+        }
+    }
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Client::new()
+    }
+}
+
+/// An asynchronous client for a Searchfox instance.
+#[derive(Clone, Debug)]
+pub struct AsyncClient<T = AsyncReqwestTransport> {
+    base_url: Url,
+    transport: T,
+    strict: bool,
+    reject_timed_out: bool,
+}
+
+impl AsyncClient {
+    /// Create a new client for [searchfox.org](https://searchfox.org).
+    pub fn new() -> Self {
+        AsyncClient::with_base_url(default_base_url())
+    }
+
+    /// Create a new client for the Searchfox instance at the given URL.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `base_url` cannot be a base URL (e.g., a `data:`
+    /// URL).
+    pub fn with_base_url(base_url: Url) -> Self {
+        AsyncClient::with_transport(base_url, AsyncReqwestTransport::new())
+    }
+}
+
+impl<T: AsyncTransport> AsyncClient<T> {
+    /// Create a new client for the Searchfox instance at the given URL that
+    /// issues requests with the given transport.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `base_url` cannot be a base URL (e.g., a `data:`
+    /// URL).
+    pub fn with_transport(base_url: Url, transport: T) -> Self {
+        check_base_url(&base_url);
+
+        AsyncClient {
+            base_url,
+            transport,
+            strict: false,
+            reject_timed_out: false,
+        }
+    }
+
+    /// Set whether or not responses are parsed in strict mode.
+    ///
+    /// In strict mode, responses containing categories of results that are not
+    /// understood are rejected with an
+    /// [`Error::UnknownCategory`](enum.Error.html#variant.UnknownCategory).
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Set whether or not responses that timed out are rejected.
+    ///
+    /// By default, when Searchfox times out the partial results are returned
+    /// with [`Response::timedout`](struct.Response.html#structfield.timedout)
+    /// set. When this is set, they are instead returned in an
+    /// [`Error::TimedOut`](enum.Error.html#variant.TimedOut), e.g., for retry
+    /// logic to branch on.
+    pub fn reject_timed_out(mut self, reject_timed_out: bool) -> Self {
+        self.reject_timed_out = reject_timed_out;
+        self
+    }
+
+    /// The URL of the Searchfox instance.
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// The transport used to issue requests.
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Perform a search.
+    pub fn search(
+        &self,
+        query: &SearchQuery,
+    ) -> impl Future<Item = Response, Error = Error> + Send {
+        self.get(query.url(&self.base_url))
+    }
+
+    /// Retrieve the search results at the given URL.
+    ///
+    /// The URL must point to the `/search` endpoint of a Searchfox repository.
+    pub fn get(&self, url: Url) -> impl Future<Item = Response, Error = Error> + Send {
+        let strict = self.strict;
+        let reject_timed_out = self.reject_timed_out;
+        self.transport.get(&url).then(move |result| match result {
+            Ok(rsp) => decode(url, rsp, strict, reject_timed_out),
+            Err(e) => Err(Error::transport(url, e)),
+        })
+    }
+}
+
+impl Default for AsyncClient {
+    fn default() -> Self {
+        AsyncClient::new()
+    }
+}
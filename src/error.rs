@@ -0,0 +1,220 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::error;
+use std::fmt;
+use std::io;
+
+use reqwest::{StatusCode, Url};
+
+use crate::api::query::QueryError;
+use crate::api::repo::{RepoConfigError, UnknownRepoError};
+use crate::api::response::Response;
+use crate::transport::TransportError;
+
+/// A specialized `Result` type for this crate.
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+/// An error that occurs when interacting with Searchfox.
+#[derive(Debug)]
+pub enum Error {
+    /// The query was invalid and was not sent.
+    InvalidQuery(QueryError),
+
+    /// A repository name could not be resolved.
+    UnknownRepo(UnknownRepoError),
+
+    /// A repository configuration file could not be loaded.
+    RepoConfig(RepoConfigError),
+
+    /// The transport failed to issue the request.
+    Transport {
+        /// The URL that was requested.
+        url: Url,
+
+        /// The error raised by the transport.
+        source: TransportError,
+    },
+
+    /// The transport timed out before a response was received.
+    Timeout {
+        /// The URL that was requested.
+        url: Url,
+    },
+
+    /// Searchfox responded with an unsuccessful status code.
+    Status {
+        /// The URL that was requested.
+        url: Url,
+
+        /// The status code of the response.
+        status: StatusCode,
+    },
+
+    /// The response could not be decoded.
+    Decode {
+        /// The URL that was requested, if the response came from a request.
+        url: Option<Url>,
+
+        /// The error raised while decoding.
+        source: serde_json::Error,
+    },
+
+    /// The response contained a category of results that is not understood.
+    ///
+    /// This is only raised in strict mode (see
+    /// [`Response::into_strict`](struct.Response.html#method.into_strict)).
+    UnknownCategory {
+        /// The URL that was requested, if the response came from a request.
+        url: Option<Url>,
+
+        /// The key of the unknown category, e.g., `"IDL (nsIBrowserChild)"`.
+        key: String,
+    },
+
+    /// Searchfox timed out while searching.
+    ///
+    /// The partial results that Searchfox returned are included. This is only
+    /// returned by clients that reject timed out responses (see
+    /// [`Client::reject_timed_out`](struct.Client.html#method.reject_timed_out)).
+    TimedOut {
+        /// The URL that was requested.
+        url: Url,
+
+        /// The incomplete response.
+        partial: Box<Response>,
+    },
+}
+
+impl Error {
+    /// The URL of the request that caused the error, if any.
+    pub fn url(&self) -> Option<&Url> {
+        match self {
+            Error::Transport { url, .. }
+            | Error::Timeout { url }
+            | Error::Status { url, .. }
+            | Error::TimedOut { url, .. } => Some(url),
+            Error::Decode { url, .. } | Error::UnknownCategory { url, .. } => url.as_ref(),
+            Error::InvalidQuery(..) | Error::UnknownRepo(..) | Error::RepoConfig(..) => None,
+        }
+    }
+
+    /// Whether or not the error was caused by a timeout, either in the
+    /// transport or in Searchfox itself.
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Error::Timeout { .. } | Error::TimedOut { .. })
+    }
+
+    /// Create an error for a failed transport request.
+    ///
+    /// Timeouts raised by `reqwest` or as an `io::Error` become
+    /// [`Error::Timeout`](enum.Error.html#variant.Timeout).
+    pub(crate) fn transport(url: Url, source: TransportError) -> Self {
+        let is_timeout = if let Some(e) = source.downcast_ref::<reqwest::Error>() {
+            e.is_timeout()
+        } else if let Some(e) = source.downcast_ref::<io::Error>() {
+            e.kind() == io::ErrorKind::TimedOut
+        } else {
+            false
+        };
+
+        if is_timeout {
+            Error::Timeout { url }
+        } else {
+            Error::Transport { url, source }
+        }
+    }
+
+    /// Create an error for a response that could not be decoded.
+    pub(crate) fn decode(source: serde_json::Error) -> Self {
+        Error::Decode { url: None, source }
+    }
+
+    /// Attach the URL of the request to a decoding error.
+    pub(crate) fn with_url(mut self, request_url: Url) -> Self {
+        match &mut self {
+            Error::Decode { url, .. } | Error::UnknownCategory { url, .. } => {
+                *url = Some(request_url)
+            }
+            _ => (),
+        }
+
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidQuery(e) => write!(f, "invalid query: {}", e),
+            Error::UnknownRepo(e) => e.fmt(f),
+            Error::RepoConfig(e) => e.fmt(f),
+            Error::Transport { url, source } => write!(f, "GET {} failed: {}", url, source),
+            Error::Timeout { url } => write!(f, "GET {} timed out", url),
+            Error::Status { url, status } => write!(f, "GET {} returned {}", url, status),
+            Error::Decode {
+                url: Some(url),
+                source,
+            } => write!(f, "could not decode response from {}: {}", url, source),
+            Error::Decode { url: None, source } => {
+                write!(f, "could not decode response: {}", source)
+            }
+            Error::UnknownCategory {
+                url: Some(url),
+                key,
+            } => {
+                write!(f, "unknown category `{}' in response from {}", key, url)
+            }
+            Error::UnknownCategory { url: None, key } => {
+                write!(f, "unknown category `{}' in response", key)
+            }
+            Error::TimedOut { url, .. } => {
+                write!(f, "Searchfox timed out while searching {}", url)
+            }
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::InvalidQuery(e) => Some(e),
+            Error::UnknownRepo(e) => Some(e),
+            Error::RepoConfig(e) => Some(e),
+            Error::Transport { source, .. } => Some(&**source),
+            Error::Decode { source, .. } => Some(source),
+            Error::Timeout { .. }
+            | Error::Status { .. }
+            | Error::UnknownCategory { .. }
+            | Error::TimedOut { .. } => None,
+        }
+    }
+}
+
+impl From<QueryError> for Error {
+    fn from(e: QueryError) -> Self {
+        Error::InvalidQuery(e)
+    }
+}
+
+impl From<UnknownRepoError> for Error {
+    fn from(e: UnknownRepoError) -> Self {
+        Error::UnknownRepo(e)
+    }
+}
+
+impl From<RepoConfigError> for Error {
+    fn from(e: RepoConfigError) -> Self {
+        Error::RepoConfig(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::decode(e)
+    }
+}
@@ -11,5 +11,37 @@
 //! including mozilla-central where Firefox is developed.
 
 mod api;
+mod client;
+mod error;
+mod render;
+mod transport;
 
+pub use reqwest::{StatusCode, Url};
+
+pub use crate::api::borrowed;
+
+pub use crate::api::diff::*;
+pub use crate::api::hit::*;
+pub use crate::api::merge::*;
+pub use crate::api::path::*;
+pub use crate::api::peek::*;
+pub use crate::api::query::*;
+pub use crate::api::repo::*;
 pub use crate::api::response::*;
+pub use crate::api::stats::*;
+pub use crate::api::stream::*;
+pub use crate::api::symbol::*;
+pub use crate::api::tree::*;
+pub use crate::client::*;
+pub use crate::error::*;
+pub use crate::render::editor::*;
+pub use crate::render::grep::*;
+pub use crate::render::html::*;
+pub use crate::render::records::*;
+pub use crate::render::sarif::*;
+pub use crate::transport::*;
+
+#[cfg(test)]
+mod test_client;
+#[cfg(test)]
+mod test_error;
@@ -0,0 +1,23 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+pub mod editor;
+pub mod grep;
+pub mod html;
+pub mod records;
+pub mod sarif;
+
+#[cfg(test)]
+mod test_editor;
+#[cfg(test)]
+mod test_grep;
+#[cfg(test)]
+mod test_html;
+#[cfg(test)]
+mod test_records;
+#[cfg(test)]
+mod test_sarif;
@@ -0,0 +1,103 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::io::{self, Write};
+
+use crate::api::hit::Hit;
+
+/// A format for jumping to hits from an editor.
+///
+/// Each hit is rendered on its own line with its path, line number, and
+/// column, followed by a message containing the kind of hit, the symbol (if
+/// any), and the text of the line, e.g.:
+///
+/// ```text
+/// dom/ipc/BrowserChild.h:149:7:Definition (BrowserChild): class BrowserChild final
+/// ```
+///
+/// File name matches are placed on the first line of the file.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum EditorFormat {
+    /// Vim's quickfix list, as parsed by the default `errorformat`
+    /// (`%f:%l:%c:%m`).
+    ///
+    /// Columns are byte offsets, as Vim expects.
+    Vim,
+
+    /// Emacs' `compilation-mode`, which understands the GNU error message
+    /// format (`file:line:column: message`).
+    ///
+    /// Columns are character offsets.
+    Emacs,
+
+    /// Lines for a VS Code problem matcher, with a severity of `info`.
+    ///
+    /// Columns are offsets in UTF-16 code units, as VS Code expects. The
+    /// lines can be matched with the pattern
+    /// `^(.*):(\d+):(\d+):\s+(info):\s+(.*)$`.
+    VsCode,
+}
+
+impl EditorFormat {
+    /// Format a single hit, without a trailing newline.
+    pub fn format_hit(self, hit: &Hit) -> String {
+        let (number, column, text) = match hit.line {
+            Some(line) => {
+                let start = line.match_range().start;
+                let prefix = &line.line[..start];
+                let column = match self {
+                    EditorFormat::Vim => start,
+                    EditorFormat::Emacs => prefix.chars().count(),
+                    EditorFormat::VsCode => prefix.encode_utf16().count(),
+                };
+
+                (line.number, column + 1, Some(line.line.trim()))
+            }
+            None => (1, 1, None),
+        };
+
+        let mut message = hit.kind.name().to_owned();
+        if let Some(symbol) = hit.symbol {
+            message.push_str(&format!(" ({})", symbol));
+        }
+        if let Some(text) = text {
+            message.push_str(": ");
+            message.push_str(text);
+        }
+
+        match self {
+            EditorFormat::Vim => format!("{}:{}:{}:{}", hit.path, number, column, message),
+            EditorFormat::Emacs => format!("{}:{}:{}: {}", hit.path, number, column, message),
+            EditorFormat::VsCode => {
+                format!("{}:{}:{}: info: {}", hit.path, number, column, message)
+            }
+        }
+    }
+
+    /// Render hits to a writer, one per line.
+    pub fn render<'a, I, W>(self, hits: I, mut w: W) -> io::Result<()>
+    where
+        I: IntoIterator<Item = Hit<'a>>,
+        W: Write,
+    {
+        for hit in hits {
+            writeln!(w, "{}", self.format_hit(&hit))?;
+        }
+
+        Ok(())
+    }
+
+    /// Render hits to a string, one per line.
+    pub fn render_to_string<'a, I>(self, hits: I) -> String
+    where
+        I: IntoIterator<Item = Hit<'a>>,
+    {
+        hits.into_iter()
+            .map(|hit| self.format_hit(&hit) + "\n")
+            .collect()
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::io::{self, Write};
+
+use crate::api::hit::{Hit, Section};
+
+static PATH_STYLE: &str = "\x1b[35m";
+static NUMBER_STYLE: &str = "\x1b[32m";
+static MATCH_STYLE: &str = "\x1b[1;31m";
+static HEADER_STYLE: &str = "\x1b[1m";
+static CONTEXT_STYLE: &str = "\x1b[2m";
+static RESET: &str = "\x1b[0m";
+
+/// Renders hits in the style of `grep` and `ripgrep`.
+///
+/// Each line match is rendered as `path:line:column: text`, where the column
+/// is the 1-based byte offset of the match in the line. File name matches are
+/// rendered as just the path.
+///
+/// ```
+/// # use searchfox_api::{GrepRenderer, Response};
+/// # fn main() -> std::io::Result<()> {
+/// # let rsp: Response = r#"{"*title*": "", "*timedout*": false}"#.parse().unwrap();
+/// let stdout = std::io::stdout();
+/// GrepRenderer::new()
+///     .headers(true)
+///     .render(rsp.hits(), stdout.lock())?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct GrepRenderer {
+    color: bool,
+    headers: bool,
+    context: bool,
+}
+
+impl GrepRenderer {
+    /// Create a renderer without color, headers, or context.
+    pub fn new() -> Self {
+        GrepRenderer::default()
+    }
+
+    /// Set whether or not to highlight output with ANSI escape codes.
+    ///
+    /// The matched text of each line is highlighted.
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set whether or not to print a header before each section and category
+    /// of results, e.g., `normal: Uses (BrowserChild)`.
+    pub fn headers(mut self, headers: bool) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Set whether or not to print the context of each match (e.g., the
+    /// function it is in) after the line.
+    pub fn context(mut self, context: bool) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Render hits to a writer.
+    pub fn render<'a, I, W>(&self, hits: I, mut w: W) -> io::Result<()>
+    where
+        I: IntoIterator<Item = Hit<'a>>,
+        W: Write,
+    {
+        let mut current: Option<(Section, String)> = None;
+
+        for hit in hits {
+            if self.headers {
+                let group = (hit.section, hit.category());
+
+                if current.as_ref() != Some(&group) {
+                    if current.is_some() {
+                        writeln!(w)?;
+                    }
+
+                    self.styled(&mut w, HEADER_STYLE, &format!("{}: {}", group.0, group.1))?;
+                    writeln!(w)?;
+                    current = Some(group);
+                }
+            }
+
+            self.render_hit(&hit, &mut w)?;
+        }
+
+        Ok(())
+    }
+
+    /// Render hits to a string.
+    pub fn render_to_string<'a, I>(&self, hits: I) -> String
+    where
+        I: IntoIterator<Item = Hit<'a>>,
+    {
+        let mut buf = vec![];
+        self.render(hits, &mut buf)
+            .expect("writing to a Vec cannot fail");
+
+        String::from_utf8(buf).expect("rendered output is valid UTF-8")
+    }
+
+    fn render_hit<W: Write>(&self, hit: &Hit, w: &mut W) -> io::Result<()> {
+        self.styled(w, PATH_STYLE, hit.path)?;
+
+        let line = match hit.line {
+            Some(line) => line,
+            None => return writeln!(w),
+        };
+
+        let range = line.match_range();
+
+        write!(w, ":")?;
+        self.styled(w, NUMBER_STYLE, &line.number.to_string())?;
+        write!(w, ":{}: {}", range.start + 1, line.prefix())?;
+        self.styled(w, MATCH_STYLE, line.matched_text())?;
+        write!(w, "{}", line.suffix())?;
+
+        if self.context {
+            if let Some(ref context) = line.context {
+                write!(w, "  ")?;
+                self.styled(w, CONTEXT_STYLE, &format!("(in {})", context.context))?;
+            }
+        }
+
+        writeln!(w)
+    }
+
+    /// Write `text`, wrapped in `style` if color is enabled.
+    fn styled<W: Write>(&self, w: &mut W, style: &str, text: &str) -> io::Result<()> {
+        if self.color && !text.is_empty() {
+            write!(w, "{}{}{}", style, text, RESET)
+        } else {
+            write!(w, "{}", text)
+        }
+    }
+}
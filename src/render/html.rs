@@ -0,0 +1,283 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::io::{self, Write};
+
+use indexmap::IndexMap;
+use reqwest::Url;
+
+use crate::api::hit::{Hit, Section};
+use crate::api::repo::Repo;
+use crate::api::response::{LineMatch, Response};
+use crate::client::{check_base_url, DEFAULT_BASE_URL};
+
+static STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; }
+summary { cursor: pointer; }
+details.section > summary { font-size: 1.25em; font-weight: bold; margin: 0.5em 0; }
+table { border-collapse: collapse; margin-bottom: 1em; width: 100%; }
+th, td { border-bottom: 1px solid #ddd; padding: 0.25em 0.5em; text-align: left; vertical-align: top; }
+td.number { text-align: right; }
+code, pre { font-family: monospace; white-space: pre-wrap; }
+pre { background: #f6f6f6; margin: 0.25em 0; padding: 0.5em; }
+mark { background: #ffeb3b; }
+.context { color: #666; font-size: 0.9em; }
+.timedout { color: #b00; }
+";
+
+/// Renders responses as a self-contained HTML report.
+///
+/// The report has no external resources or scripts. Each section of each
+/// response (e.g., `normal`) is collapsible and contains a table for each
+/// category of results. The matched text of each line is highlighted, the
+/// peek lines of a match can be expanded, and every path and line number
+/// links to the source view on Searchfox.
+///
+/// ```
+/// # use searchfox_api::{HtmlRenderer, Response};
+/// # fn main() -> std::io::Result<()> {
+/// # let rsp: Response = r#"{"*title*": "", "*timedout*": false}"#.parse().unwrap();
+/// let mut file = Vec::new();
+/// HtmlRenderer::new()
+///     .title("Uses of BrowserChild")
+///     .render(&[rsp], &mut file)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct HtmlRenderer {
+    base_url: Url,
+    repository: Repo,
+    title: String,
+}
+
+impl Default for HtmlRenderer {
+    fn default() -> Self {
+        HtmlRenderer {
+            base_url: Url::parse(DEFAULT_BASE_URL).unwrap(),
+            repository: Repo::default(),
+            title: "Searchfox report".into(),
+        }
+    }
+}
+
+impl HtmlRenderer {
+    /// Create a renderer that links to `mozilla-central` on
+    /// [searchfox.org](https://searchfox.org).
+    pub fn new() -> Self {
+        HtmlRenderer::default()
+    }
+
+    /// Set the base URL of the Searchfox instance to link to.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `base_url` cannot be a base URL (e.g., a `data:`
+    /// URL).
+    pub fn base_url(mut self, base_url: Url) -> Self {
+        check_base_url(&base_url);
+        self.base_url = base_url;
+        self
+    }
+
+    /// Set the repository to link to.
+    pub fn repository(mut self, repository: Repo) -> Self {
+        self.repository = repository;
+        self
+    }
+
+    /// Set the title of the report.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// The URL of the source view of a file, optionally at a line number.
+    pub fn source_url(&self, path: &str, number: Option<usize>) -> Url {
+        let mut url = self.base_url.clone();
+
+        url.path_segments_mut()
+            .expect("base_url was checked when it was set")
+            .pop_if_empty()
+            .push(self.repository.name())
+            .push("source")
+            .extend(path.split('/'));
+
+        if let Some(number) = number {
+            url.set_fragment(Some(&number.to_string()));
+        }
+
+        url
+    }
+
+    /// Render responses to a writer.
+    ///
+    /// Each response is rendered under a heading with its query.
+    pub fn render<'r, I, W>(&self, responses: I, mut w: W) -> io::Result<()>
+    where
+        I: IntoIterator<Item = &'r Response>,
+        W: Write,
+    {
+        writeln!(w, "<!DOCTYPE html>")?;
+        writeln!(w, "<html>")?;
+        writeln!(w, "<head>")?;
+        writeln!(w, "<meta charset=\"utf-8\">")?;
+        writeln!(w, "<title>{}</title>", escape(&self.title))?;
+        writeln!(w, "<style>\n{}</style>", STYLE)?;
+        writeln!(w, "</head>")?;
+        writeln!(w, "<body>")?;
+        writeln!(w, "<h1>{}</h1>", escape(&self.title))?;
+
+        for rsp in responses {
+            self.render_response(rsp, &mut w)?;
+        }
+
+        writeln!(w, "</body>")?;
+        writeln!(w, "</html>")
+    }
+
+    /// Render responses to a string.
+    pub fn render_to_string<'r, I>(&self, responses: I) -> String
+    where
+        I: IntoIterator<Item = &'r Response>,
+    {
+        let mut buf = vec![];
+        self.render(responses, &mut buf)
+            .expect("writing to a Vec cannot fail");
+        String::from_utf8(buf).expect("reports are UTF-8")
+    }
+
+    fn render_response<W: Write>(&self, rsp: &Response, w: &mut W) -> io::Result<()> {
+        writeln!(w, "<section class=\"response\">")?;
+        writeln!(w, "<h2>{}</h2>", escape(&rsp.title))?;
+
+        if rsp.timedout {
+            writeln!(
+                w,
+                "<p class=\"timedout\">The search timed out; results are incomplete.</p>"
+            )?;
+        }
+
+        for &section in Section::all() {
+            let hits: Vec<_> = rsp.hits().section(section).collect();
+            if hits.is_empty() {
+                continue;
+            }
+
+            writeln!(w, "<details class=\"section\" open>")?;
+            writeln!(
+                w,
+                "<summary>{} ({} {})</summary>",
+                section,
+                hits.len(),
+                if hits.len() == 1 { "hit" } else { "hits" }
+            )?;
+
+            let mut categories: IndexMap<String, Vec<Hit>> = IndexMap::new();
+            for hit in hits {
+                categories.entry(hit.category()).or_default().push(hit);
+            }
+
+            for (category, hits) in categories {
+                writeln!(w, "<h3>{}</h3>", escape(&category))?;
+                writeln!(w, "<table>")?;
+                writeln!(
+                    w,
+                    "<thead><tr><th>File</th><th>Line</th><th>Match</th></tr></thead>"
+                )?;
+                writeln!(w, "<tbody>")?;
+
+                for hit in hits {
+                    self.render_hit(&hit, w)?;
+                }
+
+                writeln!(w, "</tbody>")?;
+                writeln!(w, "</table>")?;
+            }
+
+            writeln!(w, "</details>")?;
+        }
+
+        writeln!(w, "</section>")
+    }
+
+    fn render_hit<W: Write>(&self, hit: &Hit, w: &mut W) -> io::Result<()> {
+        let number = hit.line.map(|line| line.number);
+        let url = self.source_url(hit.path, number);
+
+        write!(
+            w,
+            "<tr><td><a href=\"{}\">{}</a></td>",
+            escape(url.as_str()),
+            escape(hit.path)
+        )?;
+
+        match hit.line {
+            Some(line) => {
+                write!(
+                    w,
+                    "<td class=\"number\"><a href=\"{}\">{}</a></td><td>",
+                    escape(url.as_str()),
+                    line.number
+                )?;
+                render_line(line, w)?;
+                write!(w, "</td>")?;
+            }
+            None => write!(w, "<td></td><td></td>")?,
+        }
+
+        writeln!(w, "</tr>")
+    }
+}
+
+fn render_line<W: Write>(line: &LineMatch, w: &mut W) -> io::Result<()> {
+    write!(
+        w,
+        "<code>{}<mark>{}</mark>{}</code>",
+        escape(line.prefix()),
+        escape(line.matched_text()),
+        escape(line.suffix())
+    )?;
+
+    if let Some(ref context) = line.context {
+        if !context.context.is_empty() {
+            write!(
+                w,
+                "<div class=\"context\">in {}</div>",
+                escape(&context.context)
+            )?;
+        }
+    }
+
+    if let Some(ref peek_lines) = line.peek_lines {
+        write!(
+            w,
+            "<details class=\"peek\"><summary>Preview</summary><pre>{}</pre></details>",
+            escape(peek_lines)
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Escape text for use in HTML content and quoted attribute values.
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
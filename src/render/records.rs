@@ -0,0 +1,167 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::api::hit::{Hit, Section};
+
+/// A hit flattened into a single row, e.g., for loading into a spreadsheet or
+/// a database.
+///
+/// Fields that do not apply to a hit (e.g., the line number of a file name
+/// match) are empty.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct HitRecord<'a> {
+    /// The section of the response.
+    pub section: Section,
+
+    /// The kind of category, e.g., `"Uses"` or `"Files"`.
+    pub category: &'a str,
+
+    /// The symbol that was matched, if any.
+    pub symbol: Option<&'a str>,
+
+    /// The path of the file.
+    pub path: &'a str,
+
+    /// The line number in the file.
+    pub line_number: Option<usize>,
+
+    /// The byte offset of the start of the match in the original line.
+    pub bounds_start: Option<usize>,
+
+    /// The byte offset of the end of the match in the original line.
+    pub bounds_end: Option<usize>,
+
+    /// The context where the match was found, e.g., the enclosing function.
+    pub context: Option<&'a str>,
+
+    /// The symbol of the context.
+    pub contextsym: Option<&'a str>,
+
+    /// The text of the line that matched the query.
+    pub matched_text: Option<&'a str>,
+
+    /// The contents of the line.
+    pub line: Option<&'a str>,
+
+    /// The lines around the match.
+    pub peek_lines: Option<&'a str>,
+}
+
+impl HitRecord<'_> {
+    /// The names of the fields, in order.
+    ///
+    /// These are the column headers of CSV output and the keys of JSON Lines
+    /// output. They must be kept in the same order as the fields.
+    pub const COLUMNS: &'static [&'static str] = &[
+        "section",
+        "category",
+        "symbol",
+        "path",
+        "line_number",
+        "bounds_start",
+        "bounds_end",
+        "context",
+        "contextsym",
+        "matched_text",
+        "line",
+        "peek_lines",
+    ];
+}
+
+impl<'a> From<Hit<'a>> for HitRecord<'a> {
+    fn from(hit: Hit<'a>) -> Self {
+        let line = hit.line;
+        let context = line.and_then(|line| line.context.as_ref());
+
+        HitRecord {
+            section: hit.section,
+            category: hit.kind.category_kind(),
+            symbol: hit.symbol,
+            path: hit.path,
+            line_number: line.map(|line| line.number),
+            bounds_start: line.map(|line| line.bounds.0),
+            bounds_end: line.map(|line| line.bounds.1),
+            context: context.map(|context| context.context.as_str()),
+            contextsym: context.map(|context| context.symbol.as_str()),
+            matched_text: line.map(|line| line.matched_text()),
+            line: line.map(|line| line.line.as_str()),
+            peek_lines: line.and_then(|line| line.peek_lines.as_deref()),
+        }
+    }
+}
+
+/// A format for writing one [`HitRecord`](struct.HitRecord.html) per hit.
+///
+/// ```
+/// # use searchfox_api::{RecordFormat, Response};
+/// # fn main() -> std::io::Result<()> {
+/// # let rsp: Response = r#"{"*title*": "", "*timedout*": false}"#.parse().unwrap();
+/// let stdout = std::io::stdout();
+/// RecordFormat::Csv.render(rsp.hits(), stdout.lock())?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RecordFormat {
+    /// One JSON object per line.
+    ///
+    /// Every object has every key in
+    /// [`HitRecord::COLUMNS`](struct.HitRecord.html#associatedconstant.COLUMNS),
+    /// with `null` for empty fields.
+    JsonLines,
+
+    /// Comma-separated values, as described by RFC 4180.
+    ///
+    /// The first row is always the header, even if there are no hits. Empty
+    /// fields are empty strings.
+    Csv,
+}
+
+impl RecordFormat {
+    /// Render hits to a writer.
+    pub fn render<'a, I, W>(self, hits: I, mut w: W) -> io::Result<()>
+    where
+        I: IntoIterator<Item = Hit<'a>>,
+        W: Write,
+    {
+        match self {
+            RecordFormat::JsonLines => {
+                for hit in hits {
+                    serde_json::to_writer(&mut w, &HitRecord::from(hit))?;
+                    w.write_all(b"\n")?;
+                }
+            }
+
+            RecordFormat::Csv => {
+                let mut writer = csv::WriterBuilder::new().has_headers(false).from_writer(w);
+
+                writer.write_record(HitRecord::COLUMNS)?;
+                for hit in hits {
+                    writer.serialize(HitRecord::from(hit))?;
+                }
+                writer.flush()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Render hits to a string.
+    pub fn render_to_string<'a, I>(self, hits: I) -> String
+    where
+        I: IntoIterator<Item = Hit<'a>>,
+    {
+        let mut buf = vec![];
+        self.render(hits, &mut buf)
+            .expect("writing to a Vec cannot fail");
+        String::from_utf8(buf).expect("records are UTF-8")
+    }
+}
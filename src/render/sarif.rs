@@ -0,0 +1,198 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::io::{self, Write};
+
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+
+use crate::api::hit::{Hit, HitKind};
+use crate::api::response::LineMatch;
+
+static SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+static SARIF_VERSION: &str = "2.1.0";
+
+/// Renders hits as a [SARIF 2.1.0](https://sarifweb.azurewebsites.net/) log,
+/// for static analysis tools and dashboards.
+///
+/// Each line match becomes a result with the `note` level. Its rule is
+/// derived from the kind of hit and the symbol, e.g., `use/BrowserChild` or
+/// `text`, and its region is the matched text of the line. The context region
+/// is the peek lines of the match if they can be numbered, or else the whole
+/// line.
+///
+/// File name matches have no location in the file and are not included.
+///
+/// ```
+/// # use searchfox_api::{HitKind, Response, SarifRenderer};
+/// # fn main() -> std::io::Result<()> {
+/// # let rsp: Response = r#"{"*title*": "", "*timedout*": false}"#.parse().unwrap();
+/// let stdout = std::io::stdout();
+/// SarifRenderer::new()
+///     .pretty(true)
+///     .render(rsp.hits().kind(HitKind::Use), stdout.lock())?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SarifRenderer {
+    pretty: bool,
+}
+
+impl SarifRenderer {
+    /// Create a renderer that writes compact JSON.
+    pub fn new() -> Self {
+        SarifRenderer::default()
+    }
+
+    /// Set whether or not to pretty-print the JSON.
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Build the SARIF log for hits.
+    pub fn to_value<'a, I>(&self, hits: I) -> Value
+    where
+        I: IntoIterator<Item = Hit<'a>>,
+    {
+        let mut rules = IndexMap::new();
+        let mut results = vec![];
+
+        for hit in hits {
+            let line = match hit.line {
+                Some(line) => line,
+                None => continue,
+            };
+
+            let id = rule_id(&hit);
+            let rule_index = match rules.get_full(&id) {
+                Some((idx, _, _)) => idx,
+                None => {
+                    let category = hit.category();
+                    rules.insert(
+                        id.clone(),
+                        json!({
+                            "id": id,
+                            "shortDescription": { "text": category },
+                        }),
+                    );
+                    rules.len() - 1
+                }
+            };
+
+            results.push(result(&hit, line, id, rule_index));
+        }
+
+        json!({
+            "$schema": SARIF_SCHEMA,
+            "version": SARIF_VERSION,
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": "https://searchfox.org/",
+                        "rules": rules.into_iter().map(|(_, rule)| rule).collect::<Vec<_>>(),
+                    },
+                },
+                "columnKind": "unicodeCodePoints",
+                "results": results,
+            }],
+        })
+    }
+
+    /// Render hits to a writer.
+    pub fn render<'a, I, W>(&self, hits: I, w: W) -> io::Result<()>
+    where
+        I: IntoIterator<Item = Hit<'a>>,
+        W: Write,
+    {
+        let value = self.to_value(hits);
+
+        if self.pretty {
+            serde_json::to_writer_pretty(w, &value)?;
+        } else {
+            serde_json::to_writer(w, &value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Render hits to a string.
+    pub fn render_to_string<'a, I>(&self, hits: I) -> String
+    where
+        I: IntoIterator<Item = Hit<'a>>,
+    {
+        let mut buf = vec![];
+        self.render(hits, &mut buf)
+            .expect("writing to a Vec cannot fail");
+        String::from_utf8(buf).expect("serde_json produces UTF-8")
+    }
+}
+
+/// The id of the rule for a hit, e.g., `definition/BrowserChild`.
+fn rule_id(hit: &Hit) -> String {
+    let kind = match hit.kind {
+        HitKind::Other(kind) => kind.to_owned(),
+        kind => kind.name().to_ascii_lowercase(),
+    };
+
+    match hit.symbol {
+        Some(symbol) => format!("{}/{}", kind, symbol),
+        None => kind,
+    }
+}
+
+fn result(hit: &Hit, line: &LineMatch, rule_id: String, rule_index: usize) -> Value {
+    let range = line.match_range();
+    let column = |offset: usize| line.line[..offset].chars().count() + 1;
+
+    let mut location = json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": hit.path },
+            "region": {
+                "startLine": line.number,
+                "startColumn": column(range.start),
+                "endColumn": column(range.end),
+                "snippet": { "text": line.matched_text() },
+            },
+            "contextRegion": {
+                "startLine": line.number,
+                "endLine": line.number,
+                "snippet": { "text": line.line },
+            },
+        },
+    });
+
+    // Peek lines are only used if the line containing the match was found in
+    // them, so that their line numbers are known.
+    if let Some(peek) = line.peek() {
+        if let (Some(first), Some(last)) = (peek.lines().first(), peek.lines().last()) {
+            location["physicalLocation"]["contextRegion"] = json!({
+                "startLine": first.number,
+                "endLine": last.number,
+                "snippet": { "text": line.peek_lines },
+            });
+        }
+    }
+
+    let mut properties = json!({ "section": hit.section });
+    if let Some(ref context) = line.context {
+        properties["context"] = json!(context.context);
+        properties["contextsym"] = json!(context.symbol);
+    }
+
+    json!({
+        "ruleId": rule_id,
+        "ruleIndex": rule_index,
+        "level": "note",
+        "message": { "text": format!("{}: {}", hit.category(), line.line.trim()) },
+        "locations": [location],
+        "properties": properties,
+    })
+}
@@ -0,0 +1,79 @@
+use crate::api::hit::*;
+use crate::api::response::*;
+use crate::render::editor::*;
+
+static RSP: &str = include_str!("../../tests/BrowserChild.json");
+
+#[test]
+fn test_formats() {
+    let rsp: Response = RSP.parse().unwrap();
+    let hits = || rsp.hits().path_prefix("dom/ipc/BrowserChild.h");
+
+    assert_eq!(
+        EditorFormat::Vim.render_to_string(hits()),
+        "dom/ipc/BrowserChild.h:1:1:File\n\
+         dom/ipc/BrowserChild.h:149:7:Definition (BrowserChild): class BrowserChild final : public nsMessageManagerScriptExecutor,\n\
+         dom/ipc/BrowserChild.h:189:1:Declaration (mozilla::dom::BrowserChild::BrowserChild): BrowserChild(ContentChild* aManager, const TabId& aTabId, TabGroup* aTabGroup,\n"
+    );
+    assert_eq!(
+        EditorFormat::Emacs.render_to_string(hits().kind(HitKind::Definition)),
+        "dom/ipc/BrowserChild.h:149:7: Definition (BrowserChild): class BrowserChild final : public nsMessageManagerScriptExecutor,\n"
+    );
+    assert_eq!(
+        EditorFormat::VsCode.render_to_string(hits().kind(HitKind::Definition)),
+        "dom/ipc/BrowserChild.h:149:7: info: Definition (BrowserChild): class BrowserChild final : public nsMessageManagerScriptExecutor,\n"
+    );
+
+    let mut buf = vec![];
+    EditorFormat::Emacs.render(hits(), &mut buf).unwrap();
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        EditorFormat::Emacs.render_to_string(hits())
+    );
+}
+
+#[test]
+fn test_format_text() {
+    let rsp: Response = RSP.parse().unwrap();
+    let hit = rsp.hits().kind(HitKind::Text).next().unwrap();
+
+    assert_eq!(
+        EditorFormat::Vim.format_hit(&hit),
+        "browser/modules/AsyncTabSwitcher.jsm:360:21:Text: // constructing BrowserChild's, layer trees, etc, by showing a blank"
+    );
+}
+
+#[test]
+fn test_format_columns() {
+    let rsp: Response = include_str!("../../tests/Multibyte.json").parse().unwrap();
+    let hit = rsp
+        .hits()
+        .path_prefix("devtools/client/shared/BrowserChild.js")
+        .next()
+        .unwrap();
+
+    // The match is preceded by a three-byte character.
+    let vim = EditorFormat::Vim.format_hit(&hit);
+    let emacs = EditorFormat::Emacs.format_hit(&hit);
+    let vscode = EditorFormat::VsCode.format_hit(&hit);
+
+    assert!(vim.starts_with("devtools/client/shared/BrowserChild.js:40:29:Text: "));
+    assert!(emacs.starts_with("devtools/client/shared/BrowserChild.js:40:27: Text: "));
+    assert!(vscode.starts_with("devtools/client/shared/BrowserChild.js:40:27: info: Text: "));
+
+    // The match is preceded by a character outside the BMP, which is two
+    // UTF-16 code units.
+    let hit = rsp
+        .hits()
+        .path_prefix("toolkit/modules/BrowserChild.jsm")
+        .next()
+        .unwrap();
+
+    let vim = EditorFormat::Vim.format_hit(&hit);
+    let emacs = EditorFormat::Emacs.format_hit(&hit);
+    let vscode = EditorFormat::VsCode.format_hit(&hit);
+
+    assert!(vim.starts_with("toolkit/modules/BrowserChild.jsm:3:19:Text: "));
+    assert!(emacs.starts_with("toolkit/modules/BrowserChild.jsm:3:16: Text: "));
+    assert!(vscode.starts_with("toolkit/modules/BrowserChild.jsm:3:17: info: Text: "));
+}
@@ -0,0 +1,87 @@
+use crate::api::hit::*;
+use crate::api::response::*;
+use crate::render::grep::*;
+
+static RSP: &str = include_str!("../../tests/BrowserChild.json");
+
+#[test]
+fn test_render() {
+    let rsp: Response = RSP.parse().unwrap();
+    let output = GrepRenderer::new().render_to_string(rsp.hits().kind(HitKind::Definition));
+
+    assert_eq!(
+        output,
+        "dom/ipc/BrowserChild.h:149:7: class BrowserChild final : public nsMessageManagerScriptExecutor,\n\
+         widget/PuppetWidget.h:46:36: typedef mozilla::dom::BrowserChild BrowserChild;\n\
+         widget/nsIWidget.h:336:36: typedef mozilla::dom::BrowserChild BrowserChild;\n\
+         dom/ipc/BrowserChild.cpp:174:57: typedef nsDataHashtable<nsUint64HashKey, BrowserChild*> BrowserChildMap;\n"
+    );
+
+    let output = GrepRenderer::new().render_to_string(rsp.hits().section(Section::Generated));
+    assert_eq!(
+        output.lines().take(3).collect::<Vec<_>>(),
+        vec![
+            "__GENERATED__/dist/include/nsIBrowserChild.h",
+            "__GENERATED__/ipc/ipdl/PBrowserChild.cpp",
+            "__GENERATED__/ipc/ipdl/_ipdlheaders/mozilla/dom/PBrowserChild.h",
+        ]
+    );
+    assert_eq!(output.lines().count(), 5);
+}
+
+#[test]
+fn test_render_headers_context() {
+    let rsp: Response = RSP.parse().unwrap();
+    let output = GrepRenderer::new()
+        .headers(true)
+        .context(true)
+        .render_to_string(rsp.hits().section(Section::Normal).path_prefix("widget/"));
+
+    assert_eq!(
+        output,
+        "normal: Definitions (BrowserChild)\n\
+         widget/PuppetWidget.h:46:36: typedef mozilla::dom::BrowserChild BrowserChild;  (in mozilla::widget::PuppetWidget)\n\
+         widget/nsIWidget.h:336:36: typedef mozilla::dom::BrowserChild BrowserChild;  (in nsIWidget)\n"
+    );
+
+    let output = GrepRenderer::new()
+        .headers(true)
+        .render_to_string(rsp.hits().path_prefix("dom/base/TabGroup.h"));
+    assert_eq!(
+        output,
+        "normal: Declarations (BrowserChild)\n\
+         dom/base/TabGroup.h:29:7: class BrowserChild;\n\
+         dom/base/TabGroup.h:47:7: class BrowserChild;\n"
+    );
+
+    let output = GrepRenderer::new()
+        .headers(true)
+        .render_to_string(rsp.hits().path_prefix("dom/ipc/BrowserChild.h"));
+    assert_eq!(
+        output,
+        "normal: Files\n\
+         dom/ipc/BrowserChild.h\n\
+         \n\
+         normal: Definitions (BrowserChild)\n\
+         dom/ipc/BrowserChild.h:149:7: class BrowserChild final : public nsMessageManagerScriptExecutor,\n\
+         \n\
+         normal: Declarations (mozilla::dom::BrowserChild::BrowserChild)\n\
+         dom/ipc/BrowserChild.h:189:1: BrowserChild(ContentChild* aManager, const TabId& aTabId, TabGroup* aTabGroup,\n"
+    );
+}
+
+#[test]
+fn test_render_color() {
+    let rsp: Response = include_str!("../../tests/Multibyte.json").parse().unwrap();
+    let output = GrepRenderer::new()
+        .color(true)
+        .headers(true)
+        .render_to_string(rsp.hits().path_prefix("devtools/"));
+
+    assert_eq!(
+        output,
+        "\x1b[1mnormal: Textual Occurrences\x1b[0m\n\
+         \x1b[35mdevtools/client/shared/BrowserChild.js\x1b[0m:\x1b[32m40\x1b[0m:29:   const label = \"Onglet — \x1b[1;31mBrowserChild\x1b[0m ✓\";\n\
+         \x1b[35mdevtools/client/shared/BrowserChild.js\x1b[0m:\x1b[32m41\x1b[0m:3: é\x1b[1;31m✓BrowserChild\x1b[0m\n"
+    );
+}
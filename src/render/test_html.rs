@@ -0,0 +1,88 @@
+use reqwest::Url;
+
+use crate::api::repo::*;
+use crate::api::response::*;
+use crate::render::html::*;
+
+static RSP: &str = include_str!("../../tests/BrowserChild.json");
+
+#[test]
+fn test_html() {
+    let rsp: Response = RSP.parse().unwrap();
+    let html = HtmlRenderer::new()
+        .title("Audit <BrowserChild>")
+        .render_to_string(Some(&rsp));
+
+    assert!(html.starts_with("<!DOCTYPE html>\n<html>\n"));
+    assert!(html.ends_with("</body>\n</html>\n"));
+    assert!(html.contains("<title>Audit &lt;BrowserChild&gt;</title>"));
+    assert!(!html.contains("<script"));
+    assert!(!html.contains("<link"));
+
+    assert!(html.contains("<summary>normal (22 hits)</summary>"));
+    assert!(html.contains("<summary>test (4 hits)</summary>"));
+    assert!(html.contains("<summary>generated (5 hits)</summary>"));
+    assert!(html.contains("<h3>Uses (BrowserChild)</h3>"));
+    assert_eq!(html.matches("<details class=\"section\" open>").count(), 3);
+
+    assert!(html.contains(
+        "<tr><td><a href=\"https://searchfox.org/mozilla-central/source/dom/ipc/BrowserChild.h#149\">dom/ipc/BrowserChild.h</a></td>\
+         <td class=\"number\"><a href=\"https://searchfox.org/mozilla-central/source/dom/ipc/BrowserChild.h#149\">149</a></td>\
+         <td><code>class <mark>BrowserChild</mark> final : public nsMessageManagerScriptExecutor,</code>"
+    ));
+
+    // Lines and peek lines are escaped.
+    assert!(html.contains("const TabId&amp; aTabId"));
+    assert!(html.contains(
+        "<details class=\"peek\"><summary>Preview</summary><pre>/**\n * BrowserChild implements"
+    ));
+}
+
+#[test]
+fn test_html_responses() {
+    let rsp: Response = RSP.parse().unwrap();
+    let timedout = Response {
+        title: "nsIBrowserChild & co".into(),
+        timedout: true,
+        normal: None,
+        test: None,
+        generated: None,
+    };
+
+    let html = HtmlRenderer::new().render_to_string(vec![&rsp, &timedout]);
+
+    assert!(html.contains("<h1>Searchfox report</h1>"));
+    assert_eq!(html.matches("<section class=\"response\">").count(), 2);
+    assert!(html.contains(&format!("<h2>{}</h2>", rsp.title)));
+    assert!(html.contains("<h2>nsIBrowserChild &amp; co</h2>"));
+    assert_eq!(html.matches("class=\"timedout\"").count(), 1);
+}
+
+#[test]
+fn test_source_url() {
+    let renderer = HtmlRenderer::new();
+    assert_eq!(
+        renderer
+            .source_url("dom/ipc/BrowserChild.h", Some(149))
+            .as_str(),
+        "https://searchfox.org/mozilla-central/source/dom/ipc/BrowserChild.h#149"
+    );
+    assert_eq!(
+        renderer.source_url("a b/c#d.txt", None).as_str(),
+        "https://searchfox.org/mozilla-central/source/a%20b/c%23d.txt"
+    );
+
+    let renderer = HtmlRenderer::new()
+        .base_url(Url::parse("https://example.com/searchfox/").unwrap())
+        .repository(Repo::Custom("my-repo".into()));
+    assert_eq!(
+        renderer.source_url("README.md", Some(1)).as_str(),
+        "https://example.com/searchfox/my-repo/source/README.md#1"
+    );
+}
+
+#[test]
+#[should_panic(expected = "cannot be used as a base URL")]
+fn test_invalid_base_url() {
+    HtmlRenderer::new().base_url(Url::parse("data:text/plain,searchfox").unwrap());
+}
@@ -0,0 +1,138 @@
+use serde_json::json;
+
+use crate::api::hit::*;
+use crate::api::response::*;
+use crate::render::records::*;
+
+static RSP: &str = include_str!("../../tests/BrowserChild.json");
+
+#[test]
+fn test_json_lines() {
+    let rsp: Response = RSP.parse().unwrap();
+    let output = RecordFormat::JsonLines.render_to_string(rsp.hits());
+    let records: Vec<serde_json::Value> = output
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    assert_eq!(records.len(), 31);
+    assert!(output.ends_with('\n'));
+
+    for record in &records {
+        let keys: Vec<_> = record.as_object().unwrap().keys().cloned().collect();
+        let mut columns: Vec<_> = HitRecord::COLUMNS.iter().map(|&c| c.to_owned()).collect();
+        columns.sort();
+        assert_eq!(keys, columns);
+    }
+
+    let file = records
+        .iter()
+        .find(|record| record["category"] == "Files")
+        .unwrap();
+    assert_eq!(file["line_number"], json!(null));
+    assert_eq!(file["matched_text"], json!(null));
+
+    let definition = records
+        .iter()
+        .find(|record| record["category"] == "Definitions")
+        .unwrap();
+    assert_eq!(definition["section"], "normal");
+    assert_eq!(definition["symbol"], "BrowserChild");
+    assert_eq!(definition["path"], "dom/ipc/BrowserChild.h");
+    assert_eq!(definition["line_number"], 149);
+    assert_eq!(definition["bounds_start"], 6);
+    assert_eq!(definition["bounds_end"], 18);
+    assert_eq!(definition["matched_text"], "BrowserChild");
+    assert!(definition["peek_lines"]
+        .as_str()
+        .unwrap()
+        .contains("\n * PBrowser for more information.\n"));
+}
+
+#[test]
+fn test_csv() {
+    let rsp: Response = RSP.parse().unwrap();
+    let output = RecordFormat::Csv.render_to_string(rsp.hits().kind(HitKind::Use));
+    let mut rdr = csv::Reader::from_reader(output.as_bytes());
+
+    assert_eq!(
+        rdr.headers().unwrap().iter().collect::<Vec<_>>(),
+        HitRecord::COLUMNS
+    );
+
+    let rows: Vec<csv::StringRecord> = rdr.records().map(Result::unwrap).collect();
+    assert_eq!(rows.len(), 8);
+
+    let row = rows.iter().find(|row| !row[7].is_empty()).unwrap();
+    assert_eq!(&row[0], "normal");
+    assert_eq!(&row[1], "Uses");
+    assert_eq!(
+        &row[7],
+        "mozilla::a11y::NotificationController::WillRefresh"
+    );
+    assert!(!row[8].is_empty());
+    assert_eq!(&row[9], "BrowserChild");
+}
+
+#[test]
+fn test_csv_empty() {
+    assert_eq!(
+        RecordFormat::Csv.render_to_string(None),
+        "section,category,symbol,path,line_number,bounds_start,bounds_end,context,contextsym,matched_text,line,peek_lines\n"
+    );
+    assert_eq!(RecordFormat::JsonLines.render_to_string(None), "");
+}
+
+#[test]
+fn test_escaping() {
+    let line = LineMatch {
+        line: r#"let s = "a, b";"#.into(),
+        number: 3,
+        bounds: (9, 10),
+        peek_lines: Some("// \"quoted\", comment\r\nlet s = \"a, b\";".into()),
+        context: None,
+    };
+    let hit = Hit {
+        section: Section::Test,
+        kind: HitKind::Text,
+        symbol: None,
+        path: "a,b/test.js",
+        line: Some(&line),
+    };
+
+    let output = RecordFormat::Csv.render_to_string(Some(hit));
+    let mut rdr = csv::Reader::from_reader(output.as_bytes());
+    let row = rdr.records().next().unwrap().unwrap();
+
+    assert_eq!(&row[0], "test");
+    assert_eq!(&row[1], "Textual Occurrences");
+    assert_eq!(&row[2], "");
+    assert_eq!(&row[3], "a,b/test.js");
+    assert_eq!(&row[4], "3");
+    assert_eq!(&row[9], "a");
+    assert_eq!(&row[10], line.line);
+    assert_eq!(&row[11], line.peek_lines.as_ref().unwrap());
+
+    let output = RecordFormat::JsonLines.render_to_string(Some(hit));
+    assert_eq!(output.lines().count(), 1);
+
+    let record: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(record["line"], line.line);
+    assert_eq!(record["peek_lines"], line.peek_lines.unwrap());
+}
+
+#[test]
+fn test_columns() {
+    // The columns must match the order of the fields, which is the order
+    // they are serialized in.
+    let rsp: Response = RSP.parse().unwrap();
+    let hit = rsp.hits().kind(HitKind::Definition).next().unwrap();
+
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(true)
+        .from_writer(vec![]);
+    writer.serialize(HitRecord::from(hit)).unwrap();
+    let output = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+
+    assert_eq!(output.lines().next().unwrap(), HitRecord::COLUMNS.join(","));
+}
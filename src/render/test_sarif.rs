@@ -0,0 +1,202 @@
+use serde_json::json;
+
+use crate::api::hit::*;
+use crate::api::response::*;
+use crate::render::sarif::*;
+
+static RSP: &str = include_str!("../../tests/BrowserChild.json");
+
+#[test]
+fn test_sarif() {
+    let rsp: Response = RSP.parse().unwrap();
+    let log = SarifRenderer::new().to_value(rsp.hits());
+
+    assert_eq!(log["version"], "2.1.0");
+    assert_eq!(log["runs"].as_array().unwrap().len(), 1);
+
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "searchfox-api");
+    assert_eq!(run["columnKind"], "unicodeCodePoints");
+
+    let rules: Vec<_> = run["tool"]["driver"]["rules"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|rule| rule["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        rules,
+        vec![
+            "text",
+            "definition/BrowserChild",
+            "definition/BrowserChildMap",
+            "declaration/BrowserChild",
+            "declaration/BrowserChildMessageManager",
+            "declaration/mozilla::dom::BrowserChild::BrowserChild",
+            "use/BrowserChild",
+            "use/BrowserChildMap",
+        ]
+    );
+    assert_eq!(
+        run["tool"]["driver"]["rules"][6]["shortDescription"]["text"],
+        "Uses (BrowserChild)"
+    );
+
+    // File name matches are not included.
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 23);
+
+    let definition = results
+        .iter()
+        .find(|result| result["ruleId"] == "definition/BrowserChild")
+        .unwrap();
+    assert_eq!(definition["ruleIndex"], 1);
+    assert_eq!(definition["level"], "note");
+    assert_eq!(
+        definition["message"]["text"],
+        "Definitions (BrowserChild): class BrowserChild final : public nsMessageManagerScriptExecutor,"
+    );
+    assert_eq!(definition["properties"], json!({ "section": "normal" }));
+
+    let location = &definition["locations"][0]["physicalLocation"];
+    assert_eq!(
+        location["artifactLocation"]["uri"],
+        "dom/ipc/BrowserChild.h"
+    );
+    assert_eq!(
+        location["region"],
+        json!({
+            "startLine": 149,
+            "startColumn": 7,
+            "endColumn": 19,
+            "snippet": { "text": "BrowserChild" },
+        })
+    );
+    assert_eq!(location["contextRegion"]["startLine"], 145);
+    assert_eq!(location["contextRegion"]["endLine"], 163);
+    assert!(location["contextRegion"]["snippet"]["text"]
+        .as_str()
+        .unwrap()
+        .starts_with("/**\n * BrowserChild implements the child actor part"));
+}
+
+#[test]
+fn test_sarif_context() {
+    let rsp: Response = RSP.parse().unwrap();
+    let hit = rsp
+        .hits()
+        .kind(HitKind::Use)
+        .find(|hit| hit.line.unwrap().context.is_some())
+        .unwrap();
+    let context = hit.line.unwrap().context.as_ref().unwrap();
+
+    let log = SarifRenderer::new().to_value(Some(hit));
+    let result = &log["runs"][0]["results"][0];
+
+    assert_eq!(result["properties"]["context"], context.context.as_str());
+    assert_eq!(result["properties"]["contextsym"], context.symbol.as_str());
+
+    // Without peek lines, the context region is the whole line.
+    let line = hit.line.unwrap();
+    assert_eq!(
+        result["locations"][0]["physicalLocation"]["contextRegion"],
+        json!({
+            "startLine": line.number,
+            "endLine": line.number,
+            "snippet": { "text": line.line },
+        })
+    );
+}
+
+#[test]
+fn test_sarif_peek_not_found() {
+    let line = LineMatch {
+        line: "  return mBrowserChild;".into(),
+        number: 40,
+        bounds: (10, 22),
+        peek_lines: Some("*aResult = nullptr;\nint x;".into()),
+        context: None,
+    };
+    let hit = Hit {
+        section: Section::Normal,
+        kind: HitKind::Text,
+        symbol: None,
+        path: "dom/ipc/BrowserChild.cpp",
+        line: Some(&line),
+    };
+
+    // The peek lines cannot be numbered, so they are not used.
+    let log = SarifRenderer::new().to_value(Some(hit));
+    let location = &log["runs"][0]["results"][0]["locations"][0]["physicalLocation"];
+
+    assert_eq!(location["contextRegion"]["startLine"], 40);
+    assert_eq!(location["contextRegion"]["endLine"], 40);
+    assert_eq!(location["contextRegion"]["snippet"]["text"], line.line);
+}
+
+#[test]
+fn test_sarif_columns() {
+    let rsp: Response = include_str!("../../tests/Multibyte.json").parse().unwrap();
+    let hit = rsp
+        .hits()
+        .path_prefix("devtools/client/shared/BrowserChild.js")
+        .next()
+        .unwrap();
+
+    // The match is preceded by a three-byte character.
+    let log = SarifRenderer::new().to_value(Some(hit));
+    let region = &log["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"];
+
+    assert_eq!(region["startColumn"], 27);
+    assert_eq!(region["endColumn"], 39);
+}
+
+#[test]
+fn test_sarif_snippets() {
+    // The snippet of every region is the text between its columns.
+    for json in &[RSP, include_str!("../../tests/Multibyte.json")] {
+        let rsp: Response = json.parse().unwrap();
+        let log = SarifRenderer::new().to_value(rsp.hits());
+
+        for result in log["runs"][0]["results"].as_array().unwrap() {
+            let location = &result["locations"][0]["physicalLocation"];
+            let region = &location["region"];
+            let context = &location["contextRegion"];
+            let idx =
+                region["startLine"].as_u64().unwrap() - context["startLine"].as_u64().unwrap();
+            let line = context["snippet"]["text"]
+                .as_str()
+                .unwrap()
+                .lines()
+                .nth(idx as usize)
+                .unwrap();
+
+            let start = region["startColumn"].as_u64().unwrap() as usize - 1;
+            let end = region["endColumn"].as_u64().unwrap() as usize - 1;
+            let text: String = line.chars().skip(start).take(end - start).collect();
+
+            assert_eq!(region["snippet"]["text"], text.as_str(), "{}", line);
+        }
+    }
+}
+
+#[test]
+fn test_sarif_render() {
+    let rsp: Response = RSP.parse().unwrap();
+    let hits = || rsp.hits().kind(HitKind::Definition);
+    let log = SarifRenderer::new().to_value(hits());
+
+    let compact = SarifRenderer::new().render_to_string(hits());
+    let pretty = SarifRenderer::new().pretty(true).render_to_string(hits());
+
+    assert!(!compact.contains('\n'));
+    assert!(pretty.contains('\n'));
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&compact).unwrap(),
+        log
+    );
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&pretty).unwrap(),
+        log
+    );
+}
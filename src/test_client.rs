@@ -0,0 +1,199 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread::{self, JoinHandle};
+
+use futures::Future;
+use reqwest::{StatusCode, Url};
+
+use crate::api::query::SearchQuery;
+use crate::api::repo::Repo;
+use crate::api::response::Response;
+use crate::client::{AsyncClient, Client};
+use crate::error::Error;
+use crate::transport::{FixtureTransport, HttpResponse, MissingFixtureError};
+
+static RSP: &str = include_str!("../tests/BrowserChild.json");
+
+/// Serve a single HTTP request on a local port.
+///
+/// The returned handle resolves to the request line and headers that were
+/// received.
+fn serve_once(status: &'static str, body: &'static str) -> (Url, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+
+        for line in BufReader::new(&stream).lines() {
+            let line = line.unwrap();
+            if line.is_empty() {
+                break;
+            }
+            request.push(line);
+        }
+
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+        .unwrap();
+
+        request
+    });
+
+    (url, handle)
+}
+
+#[test]
+fn test_search() {
+    let (url, handle) = serve_once("200 OK", RSP);
+    let client = Client::with_base_url(url);
+
+    let query = SearchQuery::builder("BrowserChild").build().unwrap();
+    let rsp = client.search(&query).unwrap();
+    let expected: Response = serde_json::from_str(RSP).unwrap();
+    assert_eq!(rsp, expected);
+
+    let request = handle.join().unwrap();
+    assert_eq!(
+        request[0],
+        "GET /mozilla-central/search?q=BrowserChild&case=false&regex=false&path= HTTP/1.1"
+    );
+    assert!(request
+        .iter()
+        .any(|h| h.eq_ignore_ascii_case("accept: application/json")));
+}
+
+#[test]
+fn test_search_base_path() {
+    let (url, handle) = serve_once("200 OK", RSP);
+    let client = Client::with_base_url(url.join("/mozsearch/").unwrap());
+
+    let query = SearchQuery::builder("BrowserChild")
+        .repository(Repo::Nss)
+        .build()
+        .unwrap();
+    client.search(&query).unwrap();
+
+    let request = handle.join().unwrap();
+    assert!(request[0].starts_with("GET /mozsearch/nss/search?"));
+}
+
+#[test]
+fn test_search_status() {
+    let (base_url, handle) = serve_once("404 Not Found", "");
+    let client = Client::with_base_url(base_url.clone());
+
+    let query = SearchQuery::builder("BrowserChild").build().unwrap();
+    match client.search(&query) {
+        Err(Error::Status {
+            ref url,
+            status: StatusCode::NOT_FOUND,
+        }) if *url == query.url(&base_url) => (),
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_async_search() {
+    let (url, handle) = serve_once("200 OK", RSP);
+    let client = AsyncClient::with_base_url(url);
+
+    let query = SearchQuery::builder("BrowserChild").build().unwrap();
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let rsp = runtime.block_on(client.search(&query)).unwrap();
+    let expected: Response = serde_json::from_str(RSP).unwrap();
+    assert_eq!(rsp, expected);
+
+    let request = handle.join().unwrap();
+    assert_eq!(
+        request[0],
+        "GET /mozilla-central/search?q=BrowserChild&case=false&regex=false&path= HTTP/1.1"
+    );
+    assert!(request
+        .iter()
+        .any(|h| h.eq_ignore_ascii_case("accept: application/json")));
+}
+
+fn fixture_transport(base_url: &Url, query: &SearchQuery) -> FixtureTransport {
+    let mut transport = FixtureTransport::new();
+    transport.insert(query.url(base_url), HttpResponse::ok(RSP));
+    transport.insert(
+        base_url.join("/mozilla-central/search?q=garbage").unwrap(),
+        HttpResponse::ok("<html></html>"),
+    );
+    transport.insert(
+        base_url.join("/mozilla-central/search?q=error").unwrap(),
+        HttpResponse {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            body: vec![],
+        },
+    );
+    transport
+}
+
+#[test]
+fn test_fixture_transport() {
+    let base_url = Url::parse("https://searchfox.org").unwrap();
+    let query = SearchQuery::builder("BrowserChild").build().unwrap();
+    let client = Client::with_transport(base_url.clone(), fixture_transport(&base_url, &query));
+
+    let expected: Response = serde_json::from_str(RSP).unwrap();
+    assert_eq!(client.search(&query).unwrap(), expected);
+
+    match client.get(base_url.join("/mozilla-central/search?q=garbage").unwrap()) {
+        Err(Error::Decode { url: Some(_), .. }) => (),
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    match client.get(base_url.join("/mozilla-central/search?q=error").unwrap()) {
+        Err(Error::Status {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            ..
+        }) => (),
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    let missing = base_url.join("/nss/search?q=BrowserChild").unwrap();
+    match client.get(missing.clone()) {
+        Err(Error::Transport {
+            ref url,
+            ref source,
+        }) if *url == missing => {
+            assert_eq!(
+                source.downcast_ref::<MissingFixtureError>(),
+                Some(&MissingFixtureError(missing.clone()))
+            );
+        }
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
+
+#[test]
+fn test_async_fixture_transport() {
+    let base_url = Url::parse("https://searchfox.org").unwrap();
+    let query = SearchQuery::builder("BrowserChild").build().unwrap();
+    let client =
+        AsyncClient::with_transport(base_url.clone(), fixture_transport(&base_url, &query));
+
+    let expected: Response = serde_json::from_str(RSP).unwrap();
+    assert_eq!(client.search(&query).wait().unwrap(), expected);
+
+    match client
+        .get(base_url.join("/mozilla-central/search?q=error").unwrap())
+        .wait()
+    {
+        Err(Error::Status {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            ..
+        }) => (),
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
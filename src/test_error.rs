@@ -0,0 +1,127 @@
+use std::io;
+
+use reqwest::Url;
+
+use crate::api::query::SearchQuery;
+use crate::api::response::Response;
+use crate::client::Client;
+use crate::error::Error;
+use crate::transport::{FixtureTransport, HttpResponse, Transport, TransportError};
+
+static RSP: &str = include_str!("../tests/BrowserChild.json");
+
+static UNKNOWN_CATEGORY: &str = r#"{
+    "*timedout*": false,
+    "*title*": "nsIBrowserChild",
+    "normal": {
+        "IDL (nsIBrowserChild)": []
+    }
+}"#;
+
+#[test]
+fn test_decode() {
+    let rsp = UNKNOWN_CATEGORY.parse::<Response>().unwrap();
+    match rsp.into_strict() {
+        Err(Error::UnknownCategory { url: None, ref key }) if key == "IDL (nsIBrowserChild)" => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    let rsp = UNKNOWN_CATEGORY
+        .replace("IDL (nsIBrowserChild)", "Definitions ()")
+        .parse::<Response>()
+        .unwrap();
+    match rsp.into_strict() {
+        Err(Error::UnknownCategory { url: None, ref key }) if key == "Definitions ()" => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    let rsp = RSP.parse::<Response>().unwrap();
+    assert!(rsp.into_strict().is_ok());
+
+    match Response::from_slice(b"{\"*title*\": 3}") {
+        Err(Error::Decode { url: None, .. }) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    match Response::from_reader(&b"{"[..]) {
+        Err(Error::Decode { url: None, .. }) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
+
+#[test]
+fn test_client_errors() {
+    let base_url = Url::parse("https://searchfox.org").unwrap();
+    let query = SearchQuery::builder("nsIBrowserChild").build().unwrap();
+    let url = query.url(&base_url);
+
+    let mut transport = FixtureTransport::new();
+    transport.insert(url.clone(), HttpResponse::ok(UNKNOWN_CATEGORY));
+    let client = Client::with_transport(base_url.clone(), transport);
+    assert!(client.search(&query).is_ok());
+
+    let client = client.strict(true);
+    let err = client.search(&query).unwrap_err();
+    assert_eq!(err.url(), Some(&url));
+    match err {
+        Error::UnknownCategory { ref key, .. } if key == "IDL (nsIBrowserChild)" => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+
+    let timed_out = RSP.replace("\"*timedout*\": false", "\"*timedout*\": true");
+    let mut transport = FixtureTransport::new();
+    transport.insert(url.clone(), HttpResponse::ok(timed_out));
+    let client = Client::with_transport(base_url.clone(), transport);
+
+    let rsp = client.search(&query).unwrap();
+    assert!(rsp.timedout);
+
+    let client = client.reject_timed_out(true);
+    let err = client.search(&query).unwrap_err();
+    assert!(err.is_timeout());
+    match err {
+        Error::TimedOut { partial, .. } => {
+            assert!(partial.timedout);
+            assert_eq!(partial.title, "BrowserChild");
+        }
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+struct TimeoutTransport;
+
+impl Transport for TimeoutTransport {
+    fn get(&self, _url: &Url) -> Result<HttpResponse, TransportError> {
+        Err(io::Error::new(io::ErrorKind::TimedOut, "timed out").into())
+    }
+}
+
+#[test]
+fn test_transport_timeout() {
+    let base_url = Url::parse("https://searchfox.org").unwrap();
+    let query = SearchQuery::builder("BrowserChild").build().unwrap();
+    let client = Client::with_transport(base_url.clone(), TimeoutTransport);
+
+    let err = client.search(&query).unwrap_err();
+    assert!(err.is_timeout());
+    match err {
+        Error::Timeout { ref url } if *url == query.url(&base_url) => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn test_from() {
+    let err: Error = SearchQuery::builder("ab").build().unwrap_err().into();
+    assert!(err.url().is_none());
+    match err {
+        Error::InvalidQuery(_) => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+
+    let err: Error = "mozilla-esr1".parse::<crate::Repo>().unwrap_err().into();
+    match err {
+        Error::UnknownRepo(ref e) if e.name == "mozilla-esr1" => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright 2019 Barret Rennie.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT
+// or http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io::Read;
+
+use futures::{future, Future, Stream};
+use reqwest::header::ACCEPT;
+use reqwest::{StatusCode, Url};
+
+/// An error raised by a transport.
+pub type TransportError = Box<dyn Error + Send + Sync>;
+
+/// A future resolving to the response of an [`AsyncTransport`](trait.AsyncTransport.html).
+pub type TransportFuture = Box<dyn Future<Item = HttpResponse, Error = TransportError> + Send>;
+
+/// An HTTP response returned by a transport.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HttpResponse {
+    /// The status code of the response.
+    pub status: StatusCode,
+
+    /// The body of the response.
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Create a new successful response with the given body.
+    pub fn ok<B: Into<Vec<u8>>>(body: B) -> Self {
+        HttpResponse {
+            status: StatusCode::OK,
+            body: body.into(),
+        }
+    }
+}
+
+/// A blocking mechanism for issuing HTTP requests.
+pub trait Transport {
+    /// Issue a `GET` request for the given URL.
+    ///
+    /// Searchfox only returns JSON when it is requested, so implementations
+    /// must send an `Accept: application/json` header.
+    ///
+    /// Responses with an unsuccessful status code should be returned as an
+    /// `HttpResponse`, not as an error.
+    fn get(&self, url: &Url) -> Result<HttpResponse, TransportError>;
+}
+
+/// An asynchronous mechanism for issuing HTTP requests.
+pub trait AsyncTransport {
+    /// Issue a `GET` request for the given URL.
+    ///
+    /// The same requirements as [`Transport::get`](trait.Transport.html#tymethod.get)
+    /// apply.
+    fn get(&self, url: &Url) -> TransportFuture;
+}
+
+/// A blocking transport using `reqwest`.
+#[derive(Clone, Debug)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Create a new transport.
+    pub fn new() -> Self {
+        ReqwestTransport::with_client(reqwest::Client::new())
+    }
+
+    /// Create a new transport using the given `reqwest` client.
+    pub fn with_client(client: reqwest::Client) -> Self {
+        ReqwestTransport { client }
+    }
+}
+
+impl Default for ReqwestTransport {
+    fn default() -> Self {
+        ReqwestTransport::new()
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn get(&self, url: &Url) -> Result<HttpResponse, TransportError> {
+        let mut rsp = self
+            .client
+            .get(url.clone())
+            .header(ACCEPT, "application/json")
+            .send()?;
+
+        let mut body = Vec::new();
+        rsp.read_to_end(&mut body)?;
+
+        Ok(HttpResponse {
+            status: rsp.status(),
+            body,
+        })
+    }
+}
+
+/// An asynchronous transport using `reqwest`.
+///
+/// Requests must be driven by a `tokio` runtime.
+#[derive(Clone, Debug)]
+pub struct AsyncReqwestTransport {
+    client: reqwest::r#async::Client,
+}
+
+impl AsyncReqwestTransport {
+    /// Create a new transport.
+    pub fn new() -> Self {
+        AsyncReqwestTransport::with_client(reqwest::r#async::Client::new())
+    }
+
+    /// Create a new transport using the given `reqwest` client.
+    pub fn with_client(client: reqwest::r#async::Client) -> Self {
+        AsyncReqwestTransport { client }
+    }
+}
+
+impl Default for AsyncReqwestTransport {
+    fn default() -> Self {
+        AsyncReqwestTransport::new()
+    }
+}
+
+impl AsyncTransport for AsyncReqwestTransport {
+    fn get(&self, url: &Url) -> TransportFuture {
+        let fut = self
+            .client
+            .get(url.clone())
+            .header(ACCEPT, "application/json")
+            .send()
+            .and_then(|rsp| {
+                let status = rsp.status();
+                rsp.into_body().concat2().map(move |body| HttpResponse {
+                    status,
+                    body: body.to_vec(),
+                })
+            })
+            .map_err(|e| e.into());
+
+        Box::new(fut)
+    }
+}
+
+/// A transport that serves recorded responses.
+///
+/// This is useful for testing code that uses a client without making network
+/// requests.
+#[derive(Clone, Debug, Default)]
+pub struct FixtureTransport {
+    responses: HashMap<Url, HttpResponse>,
+}
+
+impl FixtureTransport {
+    /// Create a new transport with no recorded responses.
+    pub fn new() -> Self {
+        FixtureTransport::default()
+    }
+
+    /// Record the response for the given URL.
+    pub fn insert(&mut self, url: Url, response: HttpResponse) {
+        self.responses.insert(url, response);
+    }
+}
+
+impl Transport for FixtureTransport {
+    fn get(&self, url: &Url) -> Result<HttpResponse, TransportError> {
+        self.responses
+            .get(url)
+            .cloned()
+            .ok_or_else(|| Box::new(MissingFixtureError(url.clone())) as TransportError)
+    }
+}
+
+impl AsyncTransport for FixtureTransport {
+    fn get(&self, url: &Url) -> TransportFuture {
+        Box::new(future::result(Transport::get(self, url)))
+    }
+}
+
+/// An error raised by a [`FixtureTransport`](struct.FixtureTransport.html)
+/// when no response was recorded for a URL.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MissingFixtureError(pub Url);
+
+impl fmt::Display for MissingFixtureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no response recorded for {}", self.0)
+    }
+}
+
+impl Error for MissingFixtureError {}